@@ -1,6 +1,6 @@
 use compensated_summation::KahanBabuskaNeumaier;
 use liglicko2_research::encounter::BySpeed;
-use liglicko2_research::encounter::{RawEncounter, UtcDateTime};
+use liglicko2_research::encounter::{ResilientReader, UtcDateTime};
 use rustc_hash::FxHashSet;
 use std::error::Error as StdError;
 use std::io;
@@ -26,7 +26,7 @@ impl Stats {
 }
 
 fn main() -> Result<(), Box<dyn StdError>> {
-    let mut reader = csv::Reader::from_reader(io::stdin().lock());
+    let mut reader = ResilientReader::from_reader(io::stdin().lock());
 
     let mut last_intermediate_report = UtcDateTime::default();
 
@@ -42,8 +42,8 @@ fn main() -> Result<(), Box<dyn StdError>> {
         Stats::csv_header("correspondence"),
     );
 
-    for encounter in reader.deserialize() {
-        let encounter: RawEncounter = encounter?;
+    for encounter in &mut reader {
+        let encounter = encounter?;
 
         if encounter.utc_date_time.as_seconds()
             > last_intermediate_report.as_seconds() + 7 * 24 * 60 * 60
@@ -81,5 +81,7 @@ fn main() -> Result<(), Box<dyn StdError>> {
         }
     }
 
+    eprintln!("# Malformed rows: {}", reader.error_count());
+
     Ok(())
 }
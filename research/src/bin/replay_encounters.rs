@@ -1,13 +1,15 @@
-use std::{error::Error as StdError, fs::File, io, io::Write};
+use std::{error::Error as StdError, io, io::Write};
 
 use clap::Parser as _;
 use compensated_summation::KahanBabuskaNeumaier;
-use liglicko2::{
-    deviance, Instant, Rating, RatingDifference, RatingScalar, RatingSystem, Score, Volatility,
-};
+use liglicko2::{deviance, Instant, Rating, RatingDifference, RatingSystem, Score, Volatility};
 use liglicko2_research::{
-    encounter::{BySpeed, RawEncounter, Speed, UtcDateTime},
-    player::{ByPlayerId, PlayerId, PlayerIds},
+    checkpoint::Checkpointer,
+    deviance::DevianceBySpeed,
+    encounter::{BySpeed, Encounter, ResilientReader, Speed, UtcDateTime},
+    leaderboard::SortedRatings,
+    player::{ByPlayerId, PlayerIds},
+    stream::GameStreamExt as _,
 };
 use ordered_float::OrderedFloat;
 use rayon::prelude::*;
@@ -16,14 +18,6 @@ use uuid::Uuid;
 #[global_allocator]
 static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 
-struct Encounter {
-    white: PlayerId,
-    black: PlayerId,
-    white_score: Score,
-    utc_date_time: UtcDateTime,
-    speed: Speed,
-}
-
 #[derive(Default, Clone)]
 struct Wdl {
     wins: u64,
@@ -61,6 +55,7 @@ struct Experiment {
     total_games: u64,
     errors: u64,
     deviation_histogram: DeviationHistogram,
+    deviance_by_speed: DevianceBySpeed,
 }
 
 impl Experiment {
@@ -69,53 +64,39 @@ impl Experiment {
     }
 
     fn to_instant(&self, timestamp: UtcDateTime) -> Instant {
-        Instant(timestamp.as_seconds() as f64 / (60.0 * 60.0 * 24.0) * self.rating_periods_per_day)
+        timestamp.to_instant(self.rating_periods_per_day)
     }
 
     fn batch_encounters(&mut self, encounters: &[Encounter]) {
-        for encounter in encounters {
-            self.encounter(encounter);
-        }
-    }
+        let rating_periods_per_day = self.rating_periods_per_day;
 
-    fn encounter(&mut self, encounter: &Encounter) {
-        let now = self.to_instant(encounter.utc_date_time);
-        let leaderboard = self.leaderboard.get_mut(encounter.speed);
-
-        let white = leaderboard
-            .get(encounter.white)
-            .cloned()
-            .unwrap_or_else(|| self.rating_system.new_rating());
-
-        let black = leaderboard
-            .get(encounter.black)
-            .cloned()
-            .unwrap_or_else(|| self.rating_system.new_rating());
-
-        self.deviation_histogram
-            .record(white.deviation, encounter.white_score);
-        self.deviation_histogram
-            .record(black.deviation, encounter.white_score.opposite());
-
-        self.total_deviance += deviance(
-            self.rating_system.expected_score(&white, &black, now),
-            encounter.white_score,
-        );
-        self.total_games += 1;
-
-        let (white, black) = self
-            .rating_system
-            .update_ratings(&white, &black, encounter.white_score, now)
-            .unwrap_or_else(|_| {
+        let games = encounters
+            .iter()
+            .map(|encounter| encounter.to_game(rating_periods_per_day))
+            .scan_ratings(&self.rating_system, &mut self.leaderboard);
+
+        for game in games {
+            let (speed, _) = game.white;
+            let (white, black) = &game.before;
+
+            self.deviation_histogram
+                .record(white.deviation, game.white_score);
+            self.deviation_histogram
+                .record(black.deviation, game.white_score.opposite());
+
+            let expected = self.rating_system.expected_score(white, black, game.now);
+            self.total_deviance += deviance(expected, game.white_score);
+            self.total_games += 1;
+            self.deviance_by_speed
+                .record(speed, expected, game.white_score);
+
+            if let Some(err) = game.error {
+                if self.errors == 0 {
+                    eprintln!("# First error: {:?}", err);
+                }
                 self.errors += 1;
-                (
-                    self.rating_system.new_rating(),
-                    self.rating_system.new_rating(),
-                )
-            });
-
-        leaderboard.set(encounter.white, white);
-        leaderboard.set(encounter.black, black);
+            }
+        }
     }
 
     fn avg_deviance(&self) -> f64 {
@@ -149,18 +130,17 @@ impl Experiment {
         while i < table.len() {
             if let Some(rating) = &table[i] {
                 if self.rating_system.preview_deviation(rating, at) < RatingDifference(60.0) {
-                    samples.push(OrderedFloat(f64::from(rating.rating)));
+                    samples.push(rating.rating);
                 }
             }
             i += 1 + table.len() / 100_000;
         }
 
-        samples.sort_unstable();
+        let samples = SortedRatings::from_iter(samples);
 
         let p = |x: usize| {
             samples
-                .get(samples.len() * x / 100)
-                .copied()
+                .rating_at_percentile(x as f64)
                 .map(f64::from)
                 .unwrap_or(f64::NAN)
         };
@@ -204,6 +184,14 @@ fn write_report<W: Write>(
 
     let best_experiment = experiments.last().expect("at least one experiment");
 
+    writeln!(
+        writer,
+        "# Best experiment: {} --rating-periods-per-day {}",
+        best_experiment.rating_system.to_cli_args().join(" "),
+        best_experiment.rating_periods_per_day
+    )?;
+    writeln!(writer, "# ---")?;
+
     for (speed, name) in [
         (Speed::Blitz, "thibault"),
         (Speed::Blitz, "german11"),
@@ -231,6 +219,19 @@ fn write_report<W: Write>(
         }
     }
     writeln!(writer, "# ---")?;
+    let deviance_report = best_experiment.deviance_by_speed.report();
+    writeln!(
+        writer,
+        "# Deviance by speed: ultra_bullet={:.6} bullet={:.6} blitz={:.6} rapid={:.6} classical={:.6} correspondence={:.6}, overall={:.6}",
+        deviance_report.by_speed.ultra_bullet,
+        deviance_report.by_speed.bullet,
+        deviance_report.by_speed.blitz,
+        deviance_report.by_speed.rapid,
+        deviance_report.by_speed.classical,
+        deviance_report.by_speed.correspondence,
+        deviance_report.overall,
+    )?;
+    writeln!(writer, "# ---")?;
     for speed in [
         Speed::UltraBullet,
         Speed::Bullet,
@@ -283,7 +284,7 @@ struct Opt {
 fn main() -> Result<(), Box<dyn StdError>> {
     let opt = Opt::parse();
 
-    let process_uuid = Uuid::now_v7();
+    let checkpointer = Checkpointer::new(Uuid::now_v7(), 1_000_000);
 
     let mut experiments = Vec::new();
 
@@ -295,8 +296,7 @@ fn main() -> Result<(), Box<dyn StdError>> {
                         for &rating_periods_per_day in &opt.rating_periods_per_day {
                             experiments.push(Experiment {
                                 rating_system: RatingSystem::builder()
-                                    .min_rating(RatingScalar(-f64::INFINITY))
-                                    .max_rating(RatingScalar(f64::INFINITY))
+                                    .unbounded_rating()
                                     .regulator_factor(opt.regulator_factor)
                                     .min_deviation(RatingDifference(min_deviation))
                                     .max_deviation(RatingDifference(max_deviation))
@@ -317,7 +317,7 @@ fn main() -> Result<(), Box<dyn StdError>> {
     println!("# Parallel experiments: {}", experiments.len());
     println!("# ---");
 
-    let mut reader = csv::Reader::from_reader(io::stdin().lock());
+    let mut reader = ResilientReader::from_reader(io::stdin().lock());
 
     let mut players = PlayerIds::default();
 
@@ -337,46 +337,37 @@ fn main() -> Result<(), Box<dyn StdError>> {
 
         // Dump report
         experiments.sort_by_key(Experiment::sort_key);
-        write_report(
-            File::create(format!(
-                "{}report-{}.csv",
-                if final_batch { "" } else { "progress-" },
-                process_uuid
-            ))?,
-            players,
-            &mut experiments,
-            last_date_time,
-        )?;
+        checkpointer.write("report", final_batch, |writer| {
+            write_report(writer, players, &mut experiments, last_date_time)
+        })?;
         write_report(io::stdout(), players, &mut experiments, last_date_time)?;
 
         // Dump deviation histogram for best experiment
         let best_experiment = experiments.last().expect("at least one experiment");
-        let mut deviation_histogram_file = File::create(format!(
-            "{}deviation-histogram-{}.csv",
-            if final_batch { "" } else { "progress-" },
-            process_uuid
-        ))?;
-        writeln!(deviation_histogram_file, "deviation,wins,draws,losses")?;
-        for (deviation, wdl) in best_experiment
-            .deviation_histogram
-            .buckets
-            .iter()
-            .enumerate()
-        {
-            writeln!(
-                deviation_histogram_file,
-                "{},{},{},{}",
-                deviation, wdl.wins, wdl.draws, wdl.losses
-            )?;
-        }
+        checkpointer.write("deviation-histogram", final_batch, |writer| {
+            writeln!(writer, "deviation,wins,draws,losses")?;
+            for (deviation, wdl) in best_experiment
+                .deviation_histogram
+                .buckets
+                .iter()
+                .enumerate()
+            {
+                writeln!(
+                    writer,
+                    "{},{},{},{}",
+                    deviation, wdl.wins, wdl.draws, wdl.losses
+                )?;
+            }
+            Ok(())
+        })?;
 
         Ok(())
     };
 
     let mut last_date_time = UtcDateTime::default();
 
-    for encounter in reader.deserialize() {
-        let encounter: RawEncounter = encounter?;
+    for encounter in &mut reader {
+        let encounter = encounter?;
         last_date_time = encounter.utc_date_time;
 
         batch.push(Encounter {
@@ -390,12 +381,14 @@ fn main() -> Result<(), Box<dyn StdError>> {
             utc_date_time: encounter.utc_date_time,
         });
 
-        if batch.len() >= 1_000_000 {
+        if checkpointer.is_due(batch.len()) {
             process_batch(&mut batch, &players, last_date_time, false)?;
         }
     }
 
     process_batch(&mut batch, &players, last_date_time, true)?;
 
+    eprintln!("# Malformed rows: {}", reader.error_count());
+
     Ok(())
 }
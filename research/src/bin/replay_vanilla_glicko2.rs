@@ -4,7 +4,7 @@ use compensated_summation::KahanBabuskaNeumaier;
 use glicko2::{GameResult, Glicko2Rating};
 use liglicko2::{deviance, Score};
 use liglicko2_research::{
-    encounter::{BySpeed, PgnResult, RawEncounter, UtcDateTime},
+    encounter::{BySpeed, PgnResult, ResilientReader, UtcDateTime},
     player::{ByPlayerId, PlayerIds},
 };
 use ordered_float::OrderedFloat;
@@ -115,7 +115,7 @@ impl Stats {
 }
 
 fn main() -> Result<(), Box<dyn StdError>> {
-    let mut reader = csv::Reader::from_reader(io::stdin().lock());
+    let mut reader = ResilientReader::from_reader(io::stdin().lock());
 
     let mut players = PlayerIds::default();
     let mut states: BySpeed<ByPlayerId<PlayerState>> = BySpeed::default();
@@ -130,8 +130,8 @@ fn main() -> Result<(), Box<dyn StdError>> {
         Stats::csv_header("volatility")
     );
 
-    for encounter in reader.deserialize() {
-        let encounter: RawEncounter = encounter?;
+    for encounter in &mut reader {
+        let encounter = encounter?;
         let speed = encounter.time_control.speed();
 
         // Commit rating period
@@ -237,5 +237,7 @@ fn main() -> Result<(), Box<dyn StdError>> {
         total_encounters
     );
 
+    eprintln!("# Malformed rows: {}", reader.error_count());
+
     Ok(())
 }
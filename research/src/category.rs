@@ -0,0 +1,76 @@
+use std::marker::PhantomData;
+
+/// A fixed, finite set of categories, like [`Speed`](crate::encounter::Speed).
+///
+/// # Example
+///
+/// ```
+/// use liglicko2_research::category::{ByCategory, Category};
+///
+/// #[derive(Copy, Clone)]
+/// enum Variant {
+///     Standard,
+///     Chess960,
+/// }
+///
+/// impl Category for Variant {
+///     const COUNT: usize = 2;
+///
+///     fn index(&self) -> usize {
+///         *self as usize
+///     }
+/// }
+///
+/// let mut games: ByCategory<Variant, u64> = ByCategory::default();
+/// *games.get_mut(Variant::Chess960) += 1;
+/// assert_eq!(*games.get(Variant::Standard), 0);
+/// assert_eq!(*games.get(Variant::Chess960), 1);
+/// ```
+pub trait Category {
+    /// The number of categories.
+    const COUNT: usize;
+
+    /// A unique index in `0..Self::COUNT`.
+    fn index(&self) -> usize;
+}
+
+/// Stores one value for each category of `K`.
+#[derive(Debug, Clone)]
+pub struct ByCategory<K, T> {
+    values: Vec<T>,
+    category: PhantomData<fn(K)>,
+}
+
+impl<K: Category, T: Default> Default for ByCategory<K, T> {
+    fn default() -> ByCategory<K, T> {
+        ByCategory::from_fn(|_| T::default())
+    }
+}
+
+impl<K: Category, T> ByCategory<K, T> {
+    pub fn from_fn<F>(f: F) -> ByCategory<K, T>
+    where
+        F: FnMut(usize) -> T,
+    {
+        ByCategory {
+            values: (0..K::COUNT).map(f).collect(),
+            category: PhantomData,
+        }
+    }
+
+    pub fn get(&self, category: K) -> &T {
+        &self.values[category.index()]
+    }
+
+    pub fn get_mut(&mut self, category: K) -> &mut T {
+        &mut self.values[category.index()]
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    fs::{self, File},
+    io,
+    io::BufWriter,
+    path::PathBuf,
+};
+
+use uuid::Uuid;
+
+/// Periodically writes reports of a long-running process. Progress reports
+/// are prefixed with `progress-`, and all reports are suffixed with the UUID
+/// of the process.
+///
+/// Files are first written to a temporary file, and then atomically moved
+/// into place, so that readers never see a half-written report.
+pub struct Checkpointer {
+    uuid: Uuid,
+    interval: usize,
+}
+
+impl Checkpointer {
+    pub fn new(uuid: Uuid, interval: usize) -> Checkpointer {
+        Checkpointer { uuid, interval }
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    pub fn interval(&self) -> usize {
+        self.interval
+    }
+
+    pub fn is_due(&self, pending: usize) -> bool {
+        pending >= self.interval
+    }
+
+    pub fn path(&self, name: &str, final_checkpoint: bool) -> PathBuf {
+        PathBuf::from(format!(
+            "{}{}-{}.csv",
+            if final_checkpoint { "" } else { "progress-" },
+            name,
+            self.uuid
+        ))
+    }
+
+    pub fn write<F>(&self, name: &str, final_checkpoint: bool, f: F) -> io::Result<()>
+    where
+        F: FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+    {
+        let path = self.path(name, final_checkpoint);
+        let tmp_path = path.with_extension("csv.tmp");
+
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        f(&mut writer)?;
+        writer
+            .into_inner()
+            .map_err(|err| err.into_error())?
+            .sync_all()?;
+
+        fs::rename(tmp_path, path)
+    }
+}
@@ -0,0 +1,67 @@
+use compensated_summation::KahanBabuskaNeumaier;
+use liglicko2::{deviance, Score};
+
+use crate::encounter::{BySpeed, Speed};
+
+#[derive(Default, Debug)]
+pub struct DevianceAccumulator {
+    total: KahanBabuskaNeumaier<f64>,
+    games: u64,
+}
+
+impl DevianceAccumulator {
+    pub fn record(&mut self, expected: Score, actual: Score) {
+        self.total += deviance(expected, actual);
+        self.games += 1;
+    }
+
+    pub fn total(&self) -> f64 {
+        self.total.total()
+    }
+
+    pub fn games(&self) -> u64 {
+        self.games
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.total() / self.games as f64
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct DevianceBySpeed {
+    by_speed: BySpeed<DevianceAccumulator>,
+}
+
+impl DevianceBySpeed {
+    pub fn record(&mut self, speed: Speed, expected: Score, actual: Score) {
+        self.by_speed.get_mut(speed).record(expected, actual);
+    }
+
+    pub fn report(&self) -> DevianceReport {
+        let mut total = KahanBabuskaNeumaier::default();
+        let mut games = 0;
+        for accumulator in self.by_speed.values() {
+            total += accumulator.total();
+            games += accumulator.games();
+        }
+
+        DevianceReport {
+            by_speed: BySpeed {
+                ultra_bullet: self.by_speed.ultra_bullet.mean(),
+                bullet: self.by_speed.bullet.mean(),
+                blitz: self.by_speed.blitz.mean(),
+                rapid: self.by_speed.rapid.mean(),
+                classical: self.by_speed.classical.mean(),
+                correspondence: self.by_speed.correspondence.mean(),
+            },
+            overall: total.total() / games as f64,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DevianceReport {
+    pub by_speed: BySpeed<f64>,
+    pub overall: f64,
+}
@@ -1,7 +1,9 @@
-use std::{fmt, str::FromStr};
+use std::{fmt, io, str::FromStr};
 
 use chrono::{DateTime, NaiveDateTime};
-use liglicko2::Score;
+use liglicko2::{Instant, Score};
+
+use crate::{category::Category, player::PlayerId};
 use serde::Deserialize;
 use serde_with::{serde_as, DisplayFromStr};
 use thiserror::Error;
@@ -87,6 +89,39 @@ impl UtcDateTime {
     pub fn as_seconds(self) -> i64 {
         self.0
     }
+
+    pub fn to_instant(self, rating_periods_per_day: f64) -> Instant {
+        Instant(self.0 as f64 / (60.0 * 60.0 * 24.0) * rating_periods_per_day)
+    }
+}
+
+/// Rating periods per day that worked best for Lichess data.
+pub const DEFAULT_RATING_PERIODS_PER_DAY: f64 = 0.21436;
+
+/// A rated game between two known players.
+pub struct Encounter {
+    pub white: PlayerId,
+    pub black: PlayerId,
+    pub white_score: Score,
+    pub utc_date_time: UtcDateTime,
+    pub speed: Speed,
+}
+
+impl Encounter {
+    /// The game as `(white, black, white_score, now)`, with players keyed by
+    /// speed, as expected by
+    /// [`GameStreamExt::scan_ratings()`](crate::stream::GameStreamExt::scan_ratings).
+    pub fn to_game(
+        &self,
+        rating_periods_per_day: f64,
+    ) -> ((Speed, PlayerId), (Speed, PlayerId), Score, Instant) {
+        (
+            (self.speed, self.white),
+            (self.speed, self.black),
+            self.white_score,
+            self.utc_date_time.to_instant(rating_periods_per_day),
+        )
+    }
 }
 
 #[derive(Debug)]
@@ -152,6 +187,14 @@ pub enum Speed {
     Correspondence,
 }
 
+impl Category for Speed {
+    const COUNT: usize = 6;
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct BySpeed<T> {
     pub ultra_bullet: T,
@@ -185,6 +228,17 @@ impl<T> BySpeed<T> {
         }
     }
 
+    pub fn values(&self) -> [&T; 6] {
+        [
+            &self.ultra_bullet,
+            &self.bullet,
+            &self.blitz,
+            &self.rapid,
+            &self.classical,
+            &self.correspondence,
+        ]
+    }
+
     pub fn values_mut(&mut self) -> [&mut T; 6] {
         [
             &mut self.ultra_bullet,
@@ -196,3 +250,40 @@ impl<T> BySpeed<T> {
         ]
     }
 }
+
+/// Reads encounters from CSV, skipping and counting malformed rows instead
+/// of aborting. Only I/O errors are yielded.
+pub struct ResilientReader<R> {
+    records: csv::DeserializeRecordsIntoIter<R, RawEncounter>,
+    error_count: u64,
+}
+
+impl<R: io::Read> ResilientReader<R> {
+    pub fn from_reader(reader: R) -> ResilientReader<R> {
+        ResilientReader {
+            records: csv::Reader::from_reader(reader).into_deserialize(),
+            error_count: 0,
+        }
+    }
+
+    pub fn error_count(&self) -> u64 {
+        self.error_count
+    }
+}
+
+impl<R: io::Read> Iterator for ResilientReader<R> {
+    type Item = Result<RawEncounter, csv::Error>;
+
+    fn next(&mut self) -> Option<Result<RawEncounter, csv::Error>> {
+        loop {
+            match self.records.next()? {
+                Ok(encounter) => return Some(Ok(encounter)),
+                Err(err) if err.is_io_error() => return Some(Err(err)),
+                Err(err) => {
+                    self.error_count += 1;
+                    eprintln!("# Skipping malformed row: {}", err);
+                }
+            }
+        }
+    }
+}
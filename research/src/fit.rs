@@ -0,0 +1,198 @@
+use liglicko2::{deviance, Rating, RatingDifference, RatingSystem, Score};
+
+use crate::{
+    encounter::{BySpeed, Encounter},
+    player::ByPlayerId,
+    stream::GameStreamExt as _,
+};
+
+/// Replay encounters from scratch, and calculate the average deviance of the
+/// predictions made before each game.
+///
+/// Ratings of both players are reset, if an update fails to converge.
+///
+/// # Panics
+///
+/// Panics if `encounters` is empty.
+pub fn replay_avg_deviance(
+    encounters: &[Encounter],
+    rating_system: &RatingSystem,
+    rating_periods_per_day: f64,
+) -> f64 {
+    assert!(!encounters.is_empty());
+
+    let mut leaderboard: BySpeed<ByPlayerId<Rating>> = BySpeed::default();
+
+    let total_deviance: f64 = encounters
+        .iter()
+        .map(|encounter| encounter.to_game(rating_periods_per_day))
+        .scan_ratings(rating_system, &mut leaderboard)
+        .map(|game| {
+            let (white, black) = &game.before;
+            deviance(
+                rating_system.expected_score(white, black, game.now),
+                game.white_score,
+            )
+        })
+        .sum();
+
+    total_deviance / encounters.len() as f64
+}
+
+/// Replay encounters from scratch, and calculate the mean signed difference
+/// between the expected and actual score of white.
+///
+/// Like [`replay_avg_deviance()`], ratings are updated after each game, and
+/// each prediction is made right before the update, using only the games
+/// played before it.
+///
+/// A positive value means that the rating system over-predicts white, e.g.,
+/// because the first player advantage is set too high.
+///
+/// # Panics
+///
+/// Panics if `encounters` is empty.
+pub fn calibration_error(
+    rating_system: &RatingSystem,
+    encounters: &[Encounter],
+    rating_periods_per_day: f64,
+) -> f64 {
+    assert!(!encounters.is_empty());
+
+    let mut leaderboard: BySpeed<ByPlayerId<Rating>> = BySpeed::default();
+
+    let total_error: f64 = encounters
+        .iter()
+        .map(|encounter| encounter.to_game(rating_periods_per_day))
+        .scan_ratings(rating_system, &mut leaderboard)
+        .map(|game| {
+            let (white, black) = &game.before;
+            rating_system.expected_score(white, black, game.now).value() - game.white_score.value()
+        })
+        .sum();
+
+    total_error / encounters.len() as f64
+}
+
+/// Find the first player advantage in `range` that minimizes the average
+/// deviance of replaying `encounters`, with all other parameters taken from
+/// `base_system`.
+///
+/// Uses golden-section search, assuming that the average deviance is roughly
+/// unimodal in the first player advantage. This requires far fewer replays
+/// than a grid search with comparable precision.
+///
+/// Can be alternated with [`fit_periods_per_day()`] to fit both parameters.
+///
+/// # Panics
+///
+/// Panics if `encounters` is empty.
+pub fn fit_first_advantage(
+    encounters: &[Encounter],
+    base_system: &RatingSystem,
+    rating_periods_per_day: f64,
+    (min, max): (RatingDifference, RatingDifference),
+) -> RatingDifference {
+    RatingDifference(golden_section_search(
+        |first_advantage| {
+            replay_avg_deviance(
+                encounters,
+                &base_system
+                    .to_builder()
+                    .first_advantage(RatingDifference(first_advantage))
+                    .build(),
+                rating_periods_per_day,
+            )
+        },
+        (min.0, max.0),
+        0.1,
+    ))
+}
+
+/// Convert an observed average score of the first player into the first
+/// player advantage that reproduces it in a game between two new players of
+/// `system` (with the default rating and deviation).
+///
+/// This sets the first player advantage directly from data, like the
+/// averages measured by the `new_players` binary, instead of searching for
+/// it.
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{RatingSystem, Score};
+/// use liglicko2_research::implied_first_advantage;
+///
+/// let system = RatingSystem::new();
+/// let advantage = implied_first_advantage(Score(0.53), &system);
+///
+/// let system = system.to_builder().first_advantage(advantage).build();
+/// let expected = system.expected_score(&system.new_rating(), &system.new_rating(), Default::default());
+/// assert!((expected.value() - 0.53).abs() < 1e-9);
+/// ```
+pub fn implied_first_advantage(avg_first_score: Score, system: &RatingSystem) -> RatingDifference {
+    let neutral = system
+        .to_builder()
+        .first_advantage(RatingDifference(0.0))
+        .build();
+
+    neutral.rating_gap_for_odds(
+        avg_first_score.value() / avg_first_score.opposite().value(),
+        neutral.default_deviation(),
+        neutral.default_deviation(),
+    )
+}
+
+/// Find the number of rating periods per day in `range` that minimizes the
+/// average deviance of replaying `encounters` with `system`.
+///
+/// The time scale determines how fast deviations grow between games, which
+/// interacts with the quality of predictions. Uses the same search as
+/// [`fit_first_advantage()`].
+///
+/// # Panics
+///
+/// Panics if `encounters` is empty.
+pub fn fit_periods_per_day(
+    encounters: &[Encounter],
+    system: &RatingSystem,
+    (min, max): (f64, f64),
+) -> f64 {
+    golden_section_search(
+        |rating_periods_per_day| replay_avg_deviance(encounters, system, rating_periods_per_day),
+        (min, max),
+        0.001,
+    )
+}
+
+/// Golden-section search for the minimum of `f` in `a..=b`, assuming that
+/// `f` is unimodal.
+fn golden_section_search<F>(mut f: F, (mut a, mut b): (f64, f64), tolerance: f64) -> f64
+where
+    F: FnMut(f64) -> f64,
+{
+    let inv_phi = (f64::sqrt(5.0) - 1.0) / 2.0;
+
+    let mut c = b - inv_phi * (b - a);
+    let mut d = a + inv_phi * (b - a);
+    let mut f_c = f(c);
+    let mut f_d = f(d);
+
+    while b - a > tolerance {
+        if f_c < f_d {
+            b = d;
+            d = c;
+            f_d = f_c;
+            c = b - inv_phi * (b - a);
+            f_c = f(c);
+        } else {
+            a = c;
+            c = d;
+            f_c = f_d;
+            d = a + inv_phi * (b - a);
+            f_d = f(d);
+        }
+    }
+
+    (a + b) / 2.0
+}
@@ -0,0 +1,52 @@
+use std::collections::BTreeMap;
+
+use liglicko2::{RatingDifference, Score};
+
+#[derive(Default, Clone)]
+struct Bucket {
+    total_score: f64,
+    games: u64,
+}
+
+/// Observed scores by rating gap, to validate the shape of the expected
+/// score curve against reality.
+pub struct RatingGapHistogram {
+    bucket_width: RatingDifference,
+    buckets: BTreeMap<i64, Bucket>,
+}
+
+impl RatingGapHistogram {
+    pub fn new(bucket_width: RatingDifference) -> RatingGapHistogram {
+        assert!(bucket_width > RatingDifference(0.0));
+        RatingGapHistogram {
+            bucket_width,
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    /// Record the score of a game, given the rating gap between the player
+    /// and the opponent (from the perspective of the player).
+    pub fn record(&mut self, gap: RatingDifference, score: Score) {
+        let bucket = self
+            .buckets
+            .entry((gap.0 / self.bucket_width.0).round() as i64)
+            .or_default();
+        bucket.total_score += score.value();
+        bucket.games += 1;
+    }
+
+    /// The observed average score for the center of each non-empty bucket,
+    /// with the number of games, ordered by rating gap.
+    pub fn curve(&self) -> Vec<(RatingDifference, f64, u64)> {
+        self.buckets
+            .iter()
+            .map(|(&index, bucket)| {
+                (
+                    index as f64 * self.bucket_width,
+                    bucket.total_score / bucket.games as f64,
+                    bucket.games,
+                )
+            })
+            .collect()
+    }
+}
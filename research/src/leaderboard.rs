@@ -0,0 +1,156 @@
+use std::collections::BTreeSet;
+
+use liglicko2::{Rating, RatingScalar};
+use ordered_float::OrderedFloat;
+use rustc_hash::FxHashMap;
+
+use crate::player::PlayerId;
+
+/// Ratings kept in sorted order, for fast percentile queries on a live
+/// leaderboard.
+#[derive(Debug, Clone, Default)]
+pub struct SortedRatings {
+    ratings: Vec<RatingScalar>,
+}
+
+impl SortedRatings {
+    pub fn new() -> SortedRatings {
+        SortedRatings::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ratings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ratings.is_empty()
+    }
+
+    pub fn insert(&mut self, rating: RatingScalar) {
+        let index = self.ratings.partition_point(|r| *r < rating);
+        self.ratings.insert(index, rating);
+    }
+
+    /// Removes one occurrence of `rating`. Returns `false` if it was not
+    /// present.
+    pub fn remove(&mut self, rating: RatingScalar) -> bool {
+        let index = self.ratings.partition_point(|r| *r < rating);
+        if self.ratings.get(index) == Some(&rating) {
+            self.ratings.remove(index);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Percentage of ratings strictly below `rating`, in the range
+    /// `0.0..=100.0`. `NaN` if empty.
+    pub fn percentile_of(&self, rating: RatingScalar) -> f64 {
+        let below = self.ratings.partition_point(|r| *r < rating);
+        below as f64 * 100.0 / self.ratings.len() as f64
+    }
+
+    /// The rating at percentile `p` (in the range `0.0..=100.0`), or `None`
+    /// if empty.
+    pub fn rating_at_percentile(&self, p: f64) -> Option<RatingScalar> {
+        let index = (self.ratings.len() as f64 * p / 100.0) as usize;
+        self.ratings
+            .get(index.min(self.ratings.len().saturating_sub(1)))
+            .copied()
+    }
+}
+
+impl FromIterator<RatingScalar> for SortedRatings {
+    fn from_iter<I: IntoIterator<Item = RatingScalar>>(iter: I) -> SortedRatings {
+        let mut ratings: Vec<RatingScalar> = iter.into_iter().collect();
+        ratings.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        SortedRatings { ratings }
+    }
+}
+
+/// The best `n` players by conservative rating `rating - z * deviation`,
+/// maintained incrementally for a live leaderboard.
+///
+/// Players that fall out of the top `n` are forgotten. So if a player in the
+/// top `n` drops, the structure can not know about previously evicted players
+/// that should take their place, until they are observed again.
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{RatingScalar, RatingSystem};
+/// use liglicko2_research::{leaderboard::TopN, player::PlayerIds};
+///
+/// let system = RatingSystem::new();
+/// let mut players = PlayerIds::default();
+/// let alice = players.get_or_insert("alice".to_owned());
+/// let bob = players.get_or_insert("bob".to_owned());
+/// let carol = players.get_or_insert("carol".to_owned());
+///
+/// let rated = |rating: f64| {
+///     let mut r = system.new_rating();
+///     r.rating = RatingScalar(rating);
+///     r
+/// };
+///
+/// let mut top = TopN::new(2, 2.0);
+/// top.observe(alice, rated(1600.0));
+/// top.observe(bob, rated(1700.0));
+/// top.observe(carol, rated(1500.0));
+/// assert_eq!(top.snapshot().iter().map(|(id, _)| *id).collect::<Vec<_>>(), [bob, alice]);
+///
+/// // Ratings can improve or drop.
+/// top.observe(carol, rated(1800.0));
+/// assert_eq!(top.snapshot().iter().map(|(id, _)| *id).collect::<Vec<_>>(), [carol, bob]);
+/// top.observe(bob, rated(1400.0));
+/// top.observe(alice, rated(1600.0));
+/// assert_eq!(top.snapshot().iter().map(|(id, _)| *id).collect::<Vec<_>>(), [carol, alice]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TopN {
+    n: usize,
+    z: f64,
+    by_key: BTreeSet<(OrderedFloat<f64>, PlayerId)>,
+    entries: FxHashMap<PlayerId, Rating>,
+}
+
+impl TopN {
+    pub fn new(n: usize, z: f64) -> TopN {
+        TopN {
+            n,
+            z,
+            by_key: BTreeSet::new(),
+            entries: FxHashMap::default(),
+        }
+    }
+
+    fn key(&self, rating: &Rating) -> OrderedFloat<f64> {
+        OrderedFloat(rating.rating.0 - self.z * rating.deviation.0)
+    }
+
+    /// Update the entry of a player with their current rating. In
+    /// `O(log n)`.
+    pub fn observe(&mut self, player_id: PlayerId, rating: Rating) {
+        if let Some(previous) = self.entries.remove(&player_id) {
+            self.by_key.remove(&(self.key(&previous), player_id));
+        }
+
+        self.by_key.insert((self.key(&rating), player_id));
+        self.entries.insert(player_id, rating);
+
+        if self.by_key.len() > self.n {
+            if let Some((_, evicted)) = self.by_key.pop_first() {
+                self.entries.remove(&evicted);
+            }
+        }
+    }
+
+    /// The current entries, sorted by descending conservative rating.
+    pub fn snapshot(&self) -> Vec<(PlayerId, Rating)> {
+        self.by_key
+            .iter()
+            .rev()
+            .map(|(_, player_id)| (*player_id, self.entries[player_id].clone()))
+            .collect()
+    }
+}
@@ -1,2 +1,13 @@
+pub mod category;
+pub mod checkpoint;
+pub mod deviance;
 pub mod encounter;
+pub mod fit;
+pub mod histogram;
+pub mod leaderboard;
 pub mod player;
+pub mod stream;
+
+pub use fit::{
+    calibration_error, fit_first_advantage, fit_periods_per_day, implied_first_advantage,
+};
@@ -1,6 +1,6 @@
 use rustc_hash::FxHashMap;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct PlayerId(usize);
 
 #[derive(Default)]
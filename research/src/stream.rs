@@ -0,0 +1,169 @@
+use liglicko2::{DetailedConvergenceError, Instant, Rating, RatingSystem, Score};
+
+use crate::{
+    encounter::{BySpeed, Speed},
+    player::{ByPlayerId, PlayerId},
+};
+
+/// Storage for the current ratings of players, keyed by `K`.
+pub trait RatingLookup<K> {
+    fn get(&self, key: K) -> Option<&Rating>;
+
+    fn set(&mut self, key: K, rating: Rating);
+}
+
+impl RatingLookup<PlayerId> for ByPlayerId<Rating> {
+    fn get(&self, key: PlayerId) -> Option<&Rating> {
+        ByPlayerId::get(self, key)
+    }
+
+    fn set(&mut self, key: PlayerId, rating: Rating) {
+        ByPlayerId::set(self, key, rating)
+    }
+}
+
+impl RatingLookup<(Speed, PlayerId)> for BySpeed<ByPlayerId<Rating>> {
+    fn get(&self, (speed, key): (Speed, PlayerId)) -> Option<&Rating> {
+        BySpeed::get(self, speed).get(key)
+    }
+
+    fn set(&mut self, (speed, key): (Speed, PlayerId), rating: Rating) {
+        self.get_mut(speed).set(key, rating)
+    }
+}
+
+/// A game, with the ratings of both players before and after the update.
+#[derive(Debug, Clone)]
+pub struct RatedGame<K> {
+    pub white: K,
+    pub black: K,
+    pub white_score: Score,
+    pub now: Instant,
+    /// Ratings of white and black before the game.
+    pub before: (Rating, Rating),
+    /// Ratings of white and black after the game.
+    pub after: (Rating, Rating),
+    /// Set if the update failed to converge, in which case the ratings of
+    /// both players were reset.
+    pub error: Option<DetailedConvergenceError>,
+}
+
+/// Extension trait to fold a stream of games `(white, black, white_score,
+/// now)` into evolving ratings.
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{Instant, Rating, RatingSystem, Score};
+/// use liglicko2_research::{
+///     player::{ByPlayerId, PlayerIds},
+///     stream::GameStreamExt as _,
+/// };
+///
+/// let mut players = PlayerIds::default();
+/// let alice = players.get_or_insert("alice".to_owned());
+/// let bob = players.get_or_insert("bob".to_owned());
+///
+/// let system = RatingSystem::new();
+/// let mut leaderboard: ByPlayerId<Rating> = ByPlayerId::default();
+///
+/// let games = [
+///     (alice, bob, Score::WIN, Instant(1.0)),
+///     (bob, alice, Score::LOSS, Instant(2.0)),
+/// ];
+///
+/// let deviance: f64 = games
+///     .into_iter()
+///     .scan_ratings(&system, &mut leaderboard)
+///     .map(|game| {
+///         let (white, black) = &game.before;
+///         liglicko2::deviance(system.expected_score(white, black, game.now), game.white_score)
+///     })
+///     .sum();
+/// assert!(deviance > 0.0);
+///
+/// assert!(leaderboard.get(alice).unwrap().rating > leaderboard.get(bob).unwrap().rating);
+/// ```
+pub trait GameStreamExt<K>: Iterator<Item = (K, K, Score, Instant)> + Sized {
+    /// Apply rating updates for each game, reading and writing the current
+    /// ratings in `leaderboard`. Players that are not yet in the leaderboard
+    /// start with [`RatingSystem::new_rating()`].
+    ///
+    /// The leaderboard is updated as the iterator is advanced.
+    fn scan_ratings<'a, L>(
+        self,
+        system: &'a RatingSystem,
+        leaderboard: &'a mut L,
+    ) -> ScanRatings<'a, Self, L>
+    where
+        L: RatingLookup<K>,
+    {
+        ScanRatings {
+            games: self,
+            system,
+            leaderboard,
+        }
+    }
+}
+
+impl<K, I> GameStreamExt<K> for I where I: Iterator<Item = (K, K, Score, Instant)> {}
+
+/// Iterator returned by [`GameStreamExt::scan_ratings()`].
+pub struct ScanRatings<'a, I, L> {
+    games: I,
+    system: &'a RatingSystem,
+    leaderboard: &'a mut L,
+}
+
+impl<K, I, L> Iterator for ScanRatings<'_, I, L>
+where
+    K: Copy,
+    I: Iterator<Item = (K, K, Score, Instant)>,
+    L: RatingLookup<K>,
+{
+    type Item = RatedGame<K>;
+
+    fn next(&mut self) -> Option<RatedGame<K>> {
+        let (white, black, white_score, now) = self.games.next()?;
+
+        let before = (
+            self.leaderboard
+                .get(white)
+                .cloned()
+                .unwrap_or_else(|| self.system.new_rating()),
+            self.leaderboard
+                .get(black)
+                .cloned()
+                .unwrap_or_else(|| self.system.new_rating()),
+        );
+
+        let (after, error) =
+            match self
+                .system
+                .update_ratings_diagnostic(&before.0, &before.1, white_score, now)
+            {
+                Ok(after) => (after, None),
+                Err(err) => (
+                    (self.system.new_rating(), self.system.new_rating()),
+                    Some(err),
+                ),
+            };
+
+        self.leaderboard.set(white, after.0.clone());
+        self.leaderboard.set(black, after.1.clone());
+
+        Some(RatedGame {
+            white,
+            black,
+            white_score,
+            now,
+            before,
+            after,
+            error,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.games.size_hint()
+    }
+}
@@ -0,0 +1,21 @@
+use std::{collections::HashSet, hash::BuildHasher};
+
+/// Set of game ids that have already been applied, used by
+/// [`RatingSystem::update_ratings_once()`](crate::RatingSystem::update_ratings_once).
+pub trait DedupSet {
+    /// Check if the game has already been applied.
+    fn contains(&self, game_id: u64) -> bool;
+
+    /// Mark the game as applied.
+    fn insert(&mut self, game_id: u64);
+}
+
+impl<S: BuildHasher> DedupSet for HashSet<u64, S> {
+    fn contains(&self, game_id: u64) -> bool {
+        HashSet::contains(self, &game_id)
+    }
+
+    fn insert(&mut self, game_id: u64) {
+        HashSet::insert(self, game_id);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    internal_rating::InternalRatingDifference,
+    rating_system::{expectation_value, g},
+    Instant, Rating, RatingDifference, RatingSystem, Score,
+};
+
+/// Precomputed lookup table to approximate expected scores in hot paths.
+///
+/// Caches the deviation-dependent factor of the expectation for a grid of
+/// combined deviations, and interpolates linearly between grid points.
+///
+/// # Approximation error
+///
+/// With a grid resolution of `h` (on the internal Glicko-2 scale, i.e.
+/// `resolution / internal_rating_scale`), the interpolated factor deviates
+/// from the exact one by at most `3 * h² / (8 * π²)`. The resulting error
+/// of the expected score is at most `0.23 / g` times that, where `g` is the
+/// factor for the largest possible combined deviation. With default
+/// parameters and a resolution of `1.0` rating points, the expected score
+/// is accurate to better than `1e-6`.
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{ExpectationTable, Instant, Periods, RatingDifference, RatingScalar, RatingSystem};
+///
+/// let system = RatingSystem::new();
+/// let table = ExpectationTable::new(&system, RatingDifference(1.0));
+///
+/// let mut alice = system.new_rating();
+/// alice.rating = RatingScalar(1900.0);
+/// alice.deviation = RatingDifference(83.0);
+/// let bob = system.new_rating();
+///
+/// let now = Instant::default() + Periods(3.7);
+///
+/// let exact = system.expected_score(&alice, &bob, now);
+/// let approx = table.expected_score_approx(&alice, &bob, now);
+/// assert!((exact.value() - approx.value()).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExpectationTable {
+    system: RatingSystem,
+    min_deviation: f64,
+    resolution: f64,
+    factors: Vec<f64>,
+}
+
+impl ExpectationTable {
+    /// Build a table for the given rating system, with grid points spaced
+    /// `resolution` rating points apart.
+    pub fn new(system: &RatingSystem, resolution: RatingDifference) -> ExpectationTable {
+        assert!(resolution > RatingDifference(0.0));
+
+        let scale = system.internal_rating_scale();
+        let min_deviation = InternalRatingDifference::hypot(
+            system.min_deviation().to_internal(scale),
+            system.min_deviation().to_internal(scale),
+        );
+        let max_deviation = InternalRatingDifference::hypot(
+            system.max_deviation().to_internal(scale),
+            system.max_deviation().to_internal(scale),
+        );
+        let resolution = resolution.to_internal(scale).0;
+
+        let steps = f64::ceil((max_deviation.0 - min_deviation.0) / resolution) as usize;
+        let factors = (0..=steps + 1)
+            .map(|i| {
+                g(InternalRatingDifference(
+                    min_deviation.0 + i as f64 * resolution,
+                ))
+            })
+            .collect();
+
+        ExpectationTable {
+            system: system.clone(),
+            min_deviation: min_deviation.0,
+            resolution,
+            factors,
+        }
+    }
+
+    /// The rating system that the table was built for.
+    pub fn system(&self) -> &RatingSystem {
+        &self.system
+    }
+
+    /// Approximate [`RatingSystem::expected_score()`] using the table.
+    pub fn expected_score_approx(&self, first: &Rating, second: &Rating, now: Instant) -> Score {
+        let scale = self.system.internal_rating_scale();
+        let first = self.system.clamp_rating(first);
+        let second = self.system.clamp_rating(second);
+
+        let deviation = InternalRatingDifference::hypot(
+            self.system
+                .preview_deviation(&first, now)
+                .to_internal(scale),
+            self.system
+                .preview_deviation(&second, now)
+                .to_internal(scale),
+        );
+
+        expectation_value(
+            (first.rating - second.rating + self.system.first_advantage()).to_internal(scale),
+            self.factor(deviation),
+        )
+    }
+
+    fn factor(&self, InternalRatingDifference(deviation): InternalRatingDifference) -> f64 {
+        let position = f64::max((deviation - self.min_deviation) / self.resolution, 0.0);
+        let index = usize::min(position as usize, self.factors.len() - 2);
+        let fraction = f64::min(position - index as f64, 1.0);
+        self.factors[index] + fraction * (self.factors[index + 1] - self.factors[index])
+    }
+}
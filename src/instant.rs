@@ -1,8 +1,13 @@
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
 
 /// An instant in time. A difference of `1.0` represents a *rating period* in
 /// Glicko2 terminology.
 #[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Instant(pub f64);
 
 impl From<Instant> for f64 {
@@ -19,6 +24,23 @@ impl From<f64> for Instant {
     }
 }
 
+/// Formats the instant in rating periods, with a precision of 2 decimal
+/// places unless specified otherwise.
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{Instant, Periods};
+///
+/// assert_eq!(Instant(12.3).to_string(), "12.30p");
+/// assert_eq!(format!("{:.1}", Periods(0.25)), "0.2p");
+/// ```
+impl fmt::Display for Instant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.*}p", f.precision().unwrap_or(2), self.0)
+    }
+}
+
 impl Instant {
     #[inline]
     pub fn elapsed_since(self, since: Instant) -> Periods {
@@ -69,6 +91,8 @@ impl SubAssign<Periods> for Instant {
 
 /// Number of rating periods between two instants in time.
 #[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Periods(pub f64);
 
 impl From<Periods> for f64 {
@@ -85,6 +109,14 @@ impl From<f64> for Periods {
     }
 }
 
+/// Formats the number of rating periods, with a precision of 2 decimal places
+/// unless specified otherwise.
+impl fmt::Display for Periods {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.*}p", f.precision().unwrap_or(2), self.0)
+    }
+}
+
 impl Periods {
     #[must_use]
     #[inline]
@@ -97,6 +129,30 @@ impl Periods {
     pub fn min(self, other: Periods) -> Periods {
         Periods(f64::min(self.0, other.0))
     }
+
+    /// Treat negative periods as no time elapsed.
+    ///
+    /// Rating deviations only ever grow with time. If an instant lies
+    /// before the time a rating was last updated (for example, due to clock
+    /// skew or games being processed out of order), the rating is used as-is
+    /// rather than being sharpened.
+    #[must_use]
+    #[inline]
+    pub fn clamp_non_negative(self) -> Periods {
+        self.max(Periods(0.0))
+    }
+
+    /// Limit to at most a single rating period.
+    ///
+    /// In Glicko-2, the pre-game deviation has already been decayed for
+    /// the rating period in which the game happens. With instant updates,
+    /// there is no such rating period, so Lichess decays the deviation by at
+    /// most one period of the elapsed time in step 6 of the algorithm.
+    #[must_use]
+    #[inline]
+    pub fn saturating_to_one(self) -> Periods {
+        self.min(Periods(1.0))
+    }
 }
 
 impl Add for Periods {
@@ -6,12 +6,13 @@ pub(crate) struct InternalRatingDifference(pub f64);
 impl InternalRatingDifference {
     pub fn from_external(
         RatingDifference(difference): RatingDifference,
+        scale: f64,
     ) -> InternalRatingDifference {
-        InternalRatingDifference(difference / INTERNAL_RATING_SCALE)
+        InternalRatingDifference(difference / scale)
     }
 
-    pub fn to_external(self) -> RatingDifference {
-        RatingDifference(self.0 * INTERNAL_RATING_SCALE)
+    pub fn to_external(self, scale: f64) -> RatingDifference {
+        RatingDifference(self.0 * scale)
     }
 }
 
@@ -28,4 +29,4 @@ impl InternalRatingDifference {
     }
 }
 
-const INTERNAL_RATING_SCALE: f64 = 173.7178;
+pub const DEFAULT_INTERNAL_RATING_SCALE: f64 = 173.7178;
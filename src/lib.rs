@@ -19,6 +19,21 @@
 //!   to work with fractional rating periods.
 //! - Allows considering an inherent advantage for the first player in a game.
 //!
+//! # Features
+//!
+//! - `serde`: Implement `Serialize` and `Deserialize` for [`Instant`] and
+//!   [`Periods`], as plain numbers. Round trips are exact for finite values.
+//!
+//!   ```
+//!   # #[cfg(feature = "serde")] {
+//!   use liglicko2::Instant;
+//!
+//!   let json = serde_json::to_string(&Instant(0.1)).unwrap();
+//!   assert_eq!(json, "0.1");
+//!   assert_eq!(serde_json::from_str::<Instant>(&json).unwrap(), Instant(0.1));
+//!   # }
+//!   ```
+//!
 //! # Errors
 //!
 //! When using the provided default parameters, this implementations promises:
@@ -53,44 +68,27 @@
 //! assert!(Score(0.79) < expected_score, "{expected_score:?}");
 //! ```
 
+mod dedup;
+mod expectation_table;
 mod instant;
 mod internal_rating;
+mod metrics;
+mod multi_system;
 mod rating;
 mod rating_system;
 mod score;
 
+pub use dedup::DedupSet;
+pub use expectation_table::ExpectationTable;
 pub use instant::{Instant, Periods};
-pub use rating::{Rating, RatingDifference, RatingScalar, Volatility};
-pub use rating_system::{ConvergenceError, RatingSystem, RatingSystemBuilder};
-pub use score::Score;
-
-/// Log likelihood deviance metric that can be used to evaluate the quality of
-/// rating system predictions.
-///
-/// Lower is better.
-///
-/// See <https://www.kaggle.com/c/ChessRatings2/overview/evaluation>.
-///
-/// # Example
-///
-/// ```
-/// use liglicko2::{deviance, Score};
-///
-/// let actual = Score(0.0);
-///
-/// let close_guess = deviance(Score(0.1), actual);
-/// // 0.0457...
-/// let indifferent_guess = deviance(Score(0.5), actual);
-/// // 0.3010 ...
-/// let far_guess = deviance(Score(0.95), actual);
-/// // 1.3010 ...
-///
-/// assert!(close_guess < indifferent_guess);
-/// assert!(indifferent_guess < far_guess);
-/// ```
-pub fn deviance(expected: Score, actual: Score) -> f64 {
-    let expected = expected.value().clamp(0.01, 0.99);
-    let actual = actual.value();
-
-    -(actual * expected.log10() + (1.0 - actual) * (1.0 - expected).log10())
-}
+pub use metrics::{
+    deviance, deviance_gradient_wrt_rating, expected_deviance, weighted_deviance, Attribution,
+    EwmaDeviance, LogLikelihood, WeightedDevianceAccumulator,
+};
+pub use multi_system::MultiSystem;
+pub use rating::{Rating, Rating32, RatingDifference, RatingScalar, Volatility};
+pub use rating_system::{
+    ConvergenceError, DetailedConvergenceError, OutputError, PastInstantError, PreparedRating,
+    RatingSystem, RatingSystemBuilder, RegulatorMode,
+};
+pub use score::{centipawns_from_score, score_from_centipawns, Score, CENTIPAWN_SCALE};
@@ -0,0 +1,298 @@
+use std::f64::consts::LN_10;
+
+use crate::{Instant, Rating, RatingDifference, RatingSystem, Score};
+
+/// Log likelihood deviance metric that can be used to evaluate the quality of
+/// rating system predictions.
+///
+/// Lower is better.
+///
+/// See <https://www.kaggle.com/c/ChessRatings2/overview/evaluation>.
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{deviance, Score};
+///
+/// let actual = Score(0.0);
+///
+/// let close_guess = deviance(Score(0.1), actual);
+/// // 0.0457...
+/// let indifferent_guess = deviance(Score(0.5), actual);
+/// // 0.3010 ...
+/// let far_guess = deviance(Score(0.95), actual);
+/// // 1.3010 ...
+///
+/// assert!(close_guess < indifferent_guess);
+/// assert!(indifferent_guess < far_guess);
+/// ```
+pub fn deviance(expected: Score, actual: Score) -> f64 {
+    let expected = expected.value().clamp(0.01, 0.99);
+    let actual = actual.value();
+
+    -(actual * expected.log10() + (1.0 - actual) * (1.0 - expected).log10())
+}
+
+/// Expected [`deviance`] of a prediction, assuming the prediction itself is
+/// accurate. For expected scores between `0.01` and `0.99`, this is the
+/// binary entropy of the expected score, in log10 units. Outside of that
+/// range, [`deviance`] clamps the prediction, so the result is larger than
+/// the entropy.
+///
+/// Can be used to forecast the aggregate deviance over a schedule of games
+/// before they are played.
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{deviance, expected_deviance, Score};
+///
+/// // An indifferent prediction is the most uncertain.
+/// assert!(expected_deviance(Score(0.8)) < expected_deviance(Score(0.5)));
+/// assert!((expected_deviance(Score(0.5)) - deviance(Score(0.5), Score::WIN)).abs() < 1e-12);
+/// ```
+pub fn expected_deviance(expected: Score) -> f64 {
+    let p = expected.value();
+
+    p * deviance(expected, Score::WIN) + (1.0 - p) * deviance(expected, Score::LOSS)
+}
+
+/// [`deviance`] scaled by the importance of the game. A weight of `1.0`
+/// reduces to plain deviance.
+pub fn weighted_deviance(expected: Score, actual: Score, weight: f64) -> f64 {
+    weight * deviance(expected, actual)
+}
+
+/// Accumulates the weighted average of [`deviance`], so that more
+/// important games have a larger influence on the evaluation of a rating
+/// system.
+///
+/// With all weights `1.0`, this is the plain average deviance.
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{deviance, Score, WeightedDevianceAccumulator};
+///
+/// let mut accumulator = WeightedDevianceAccumulator::default();
+/// accumulator.push(Score(0.8), Score::WIN, 3.0);
+/// accumulator.push(Score(0.3), Score::WIN, 1.0);
+///
+/// let expected = (3.0 * deviance(Score(0.8), Score::WIN) + deviance(Score(0.3), Score::WIN)) / 4.0;
+/// assert!((accumulator.mean() - expected).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WeightedDevianceAccumulator {
+    total: f64,
+    total_weight: f64,
+}
+
+impl WeightedDevianceAccumulator {
+    /// Add the deviance of a game with the given weight.
+    pub fn push(&mut self, expected: Score, actual: Score, weight: f64) {
+        self.total += weighted_deviance(expected, actual, weight);
+        self.total_weight += weight;
+    }
+
+    /// Sum of all weights pushed so far.
+    pub fn total_weight(&self) -> f64 {
+        self.total_weight
+    }
+
+    /// Weighted average deviance of all games pushed so far. `NaN` if the
+    /// total weight is zero.
+    pub fn mean(&self) -> f64 {
+        self.total / self.total_weight
+    }
+}
+
+/// Exponentially weighted moving average of [`deviance`], for monitoring
+/// the recent quality of predictions.
+///
+/// After each game, the weight of all previous games is multiplied by the
+/// decay factor. The effective window length is about `1 / (1 - decay)`
+/// games, so a decay of `0.999` averages roughly over the last 1000 games.
+/// Older games still contribute, but with exponentially diminishing weight.
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{deviance, EwmaDeviance, Score};
+///
+/// let mut ewma = EwmaDeviance::new(0.9);
+/// assert_eq!(ewma.value(), None);
+///
+/// for _ in 0..100 {
+///     ewma.push(Score(0.9), Score::WIN);
+/// }
+/// assert!((ewma.value().unwrap() - deviance(Score(0.9), Score::WIN)).abs() < 1e-12);
+///
+/// // Reacts quickly to a regression.
+/// for _ in 0..10 {
+///     ewma.push(Score(0.9), Score::LOSS);
+/// }
+/// assert!(ewma.value().unwrap() > deviance(Score(0.5), Score::LOSS));
+/// ```
+#[derive(Debug, Clone)]
+pub struct EwmaDeviance {
+    decay: f64,
+    total: f64,
+    total_weight: f64,
+}
+
+impl EwmaDeviance {
+    /// Create a new moving average with the given decay factor, in the range
+    /// `0.0..1.0`.
+    pub fn new(decay: f64) -> EwmaDeviance {
+        assert!((0.0..1.0).contains(&decay));
+        EwmaDeviance {
+            decay,
+            total: 0.0,
+            total_weight: 0.0,
+        }
+    }
+
+    /// Add the deviance of a game.
+    pub fn push(&mut self, expected: Score, actual: Score) {
+        self.total = self.decay * self.total + deviance(expected, actual);
+        self.total_weight = self.decay * self.total_weight + 1.0;
+    }
+
+    /// Moving average of the deviance, or `None` if no games have been
+    /// pushed yet.
+    pub fn value(&self) -> Option<f64> {
+        (self.total_weight > 0.0).then(|| self.total / self.total_weight)
+    }
+}
+
+/// Derivative of the [`deviance`] of the prediction for a game between
+/// `first` and `second` with respect to the rating of `first`.
+///
+/// The derivative is zero when the expected score is outside of the range
+/// where the deviance clamps its input.
+///
+/// Can be used to fit ratings or parameters like the first player advantage
+/// with gradient descent.
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{deviance, deviance_gradient_wrt_rating, Instant, RatingDifference, RatingScalar, RatingSystem, Score};
+///
+/// let system = RatingSystem::new();
+/// let mut first = system.new_rating();
+/// first.rating = RatingScalar(1600.0);
+/// let second = system.new_rating();
+/// let now = Instant(0.0);
+///
+/// let gradient = deviance_gradient_wrt_rating(&system, &first, &second, Score::LOSS, now);
+/// assert!(gradient > RatingDifference(0.0));
+///
+/// // Compare with finite difference.
+/// let h = 0.01;
+/// let deviance_at = |rating: f64| {
+///     let mut first = first.clone();
+///     first.rating = RatingScalar(rating);
+///     deviance(system.expected_score(&first, &second, now), Score::LOSS)
+/// };
+/// let approx = (deviance_at(1600.0 + h) - deviance_at(1600.0 - h)) / (2.0 * h);
+/// assert!((gradient.0 - approx).abs() < 1e-9);
+/// ```
+pub fn deviance_gradient_wrt_rating(
+    system: &RatingSystem,
+    first: &Rating,
+    second: &Rating,
+    actual: Score,
+    now: Instant,
+) -> RatingDifference {
+    RatingDifference(
+        deviance_gradient_wrt_logit(system.expected_score(first, second, now), actual)
+            * system.combined_g(first, second, now)
+            / system.internal_rating_scale(),
+    )
+}
+
+/// Derivative of the [`deviance`] with respect to the logit of the expected
+/// score. Zero when the expected score is outside of the range where the
+/// deviance clamps its input.
+pub(crate) fn deviance_gradient_wrt_logit(expected: Score, actual: Score) -> f64 {
+    let expected = expected.value();
+    if !(0.01..=0.99).contains(&expected) {
+        return 0.0;
+    }
+
+    (expected - actual.value()) / LN_10
+}
+
+/// Partial derivatives of the [`deviance`] of a prediction, as returned by
+/// [`RatingSystem::error_attribution()`].
+///
+/// All derivatives are in deviance per rating point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribution {
+    /// With respect to the rating difference between the first and second
+    /// player.
+    pub rating_gap: f64,
+    /// With respect to the combined previewed deviation of both players.
+    pub deviation: f64,
+    /// With respect to the inherent advantage of the first player.
+    pub first_advantage: f64,
+}
+
+/// Accumulates the natural log likelihood of observed outcomes, given the
+/// predicted expectations. This is the natural log analogue of [`deviance`]
+/// (up to sign), which makes it directly comparable to likelihoods of other
+/// probabilistic models.
+///
+/// Unlike [`deviance`], expectations are not clamped. A certain prediction
+/// of the wrong outcome has a log likelihood of negative infinity, so
+/// callers that need finite totals should avoid expectations of exactly
+/// `0.0` or `1.0`.
+///
+/// Higher is better.
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{LogLikelihood, Score};
+///
+/// let mut likelihood = LogLikelihood::default();
+/// likelihood.push(Score(0.8), Score::WIN);
+/// likelihood.push(Score(0.3), Score::LOSS);
+///
+/// let expected = f64::ln(0.8) + f64::ln(0.7);
+/// assert!((likelihood.total() - expected).abs() < 1e-12);
+///
+/// likelihood.push(Score::LOSS, Score::LOSS);
+/// assert!((likelihood.total() - expected).abs() < 1e-12);
+///
+/// likelihood.push(Score::LOSS, Score::WIN);
+/// assert_eq!(likelihood.total(), f64::NEG_INFINITY);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LogLikelihood {
+    total: f64,
+}
+
+impl LogLikelihood {
+    /// Add the log likelihood of the actual outcome of a game, given the
+    /// expected score.
+    pub fn push(&mut self, expected: Score, actual: Score) {
+        let expected = expected.value();
+        let actual = actual.value();
+
+        // Skip terms with zero weight, so that a correct certain prediction
+        // does not produce 0 * ln(0) = NaN.
+        if actual > 0.0 {
+            self.total += actual * expected.ln();
+        }
+        if actual < 1.0 {
+            self.total += (1.0 - actual) * (1.0 - expected).ln();
+        }
+    }
+
+    /// Total log likelihood of all outcomes pushed so far.
+    pub fn total(&self) -> f64 {
+        self.total
+    }
+}
@@ -0,0 +1,97 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{ConvergenceError, Instant, Rating, RatingSystem, Score};
+
+/// Rating systems with distinct parameters for different categories of
+/// games, like time controls.
+///
+/// Categories without explicitly configured parameters use a shared
+/// default rating system.
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{Instant, MultiSystem, RatingDifference, RatingSystem, Score};
+///
+/// #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+/// enum Speed {
+///     Blitz,
+///     Correspondence,
+/// }
+///
+/// let mut systems = MultiSystem::new(RatingSystem::new());
+/// systems.insert(
+///     Speed::Correspondence,
+///     RatingSystem::builder()
+///         .min_deviation(RatingDifference(60.0))
+///         .build(),
+/// );
+///
+/// let system = systems.get(&Speed::Blitz);
+/// let alice = system.new_rating();
+/// let bob = system.new_rating();
+///
+/// let (alice, bob) = systems
+///     .update_ratings(&Speed::Blitz, &alice, &bob, Score::WIN, Instant(1.0))
+///     .unwrap();
+/// assert!(alice.rating > bob.rating);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiSystem<K> {
+    default: RatingSystem,
+    systems: HashMap<K, RatingSystem>,
+}
+
+impl<K: Eq + Hash> Default for MultiSystem<K> {
+    fn default() -> MultiSystem<K> {
+        MultiSystem::new(RatingSystem::new())
+    }
+}
+
+impl<K: Eq + Hash> MultiSystem<K> {
+    /// Create a collection where all categories use the given rating system.
+    pub fn new(default: RatingSystem) -> MultiSystem<K> {
+        MultiSystem {
+            default,
+            systems: HashMap::new(),
+        }
+    }
+
+    /// Use a specific rating system for the given category. Returns the
+    /// previously configured rating system, if any.
+    pub fn insert(&mut self, category: K, system: RatingSystem) -> Option<RatingSystem> {
+        self.systems.insert(category, system)
+    }
+
+    /// Get the rating system for the given category.
+    pub fn get(&self, category: &K) -> &RatingSystem {
+        self.systems.get(category).unwrap_or(&self.default)
+    }
+
+    /// See [`RatingSystem::expected_score()`].
+    pub fn expected_score(
+        &self,
+        category: &K,
+        first: &Rating,
+        second: &Rating,
+        now: Instant,
+    ) -> Score {
+        self.get(category).expected_score(first, second, now)
+    }
+
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    pub fn update_ratings(
+        &self,
+        category: &K,
+        first: &Rating,
+        second: &Rating,
+        score: Score,
+        now: Instant,
+    ) -> Result<(Rating, Rating), ConvergenceError> {
+        self.get(category).update_ratings(first, second, score, now)
+    }
+}
@@ -1,6 +1,6 @@
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
-use crate::{internal_rating::InternalRatingDifference, Instant};
+use crate::{internal_rating::InternalRatingDifference, Instant, RatingSystem};
 
 /// Number representing playing strength, such that the difference between two
 /// ratings can be used to predict an expected score. Higher is better.
@@ -100,9 +100,35 @@ impl RatingDifference {
         RatingDifference(self.0.abs())
     }
 
+    /// Check if this is a gain, i.e., strictly greater than zero.
     #[inline]
-    pub(crate) fn to_internal(self) -> InternalRatingDifference {
-        InternalRatingDifference::from_external(self)
+    pub fn is_positive(self) -> bool {
+        self.0 > 0.0
+    }
+
+    /// Check if this is a loss, i.e., strictly less than zero.
+    #[inline]
+    pub fn is_negative(self) -> bool {
+        self.0 < 0.0
+    }
+
+    /// `1.0` for gains, `-1.0` for losses, and `0.0` otherwise.
+    ///
+    /// Unlike [`f64::signum()`], this is `0.0` for zero (and NaN).
+    #[inline]
+    pub fn signum(self) -> f64 {
+        if self.is_positive() {
+            1.0
+        } else if self.is_negative() {
+            -1.0
+        } else {
+            0.0
+        }
+    }
+
+    #[inline]
+    pub(crate) fn to_internal(self, scale: f64) -> InternalRatingDifference {
+        InternalRatingDifference::from_external(self, scale)
     }
 }
 
@@ -199,12 +225,55 @@ impl Volatility {
         Volatility(self.0.clamp(min.0, max.0))
     }
 
+    /// Construct a volatility from its square. Negative variances are
+    /// treated as zero.
+    #[inline]
+    pub fn from_variance(variance: f64) -> Volatility {
+        Volatility(f64::sqrt(f64::max(variance, 0.0)))
+    }
+
+    /// The square of the volatility.
+    #[inline]
+    pub fn variance(self) -> f64 {
+        self.sq()
+    }
+
+    #[inline]
+    pub fn is_zero(self) -> bool {
+        self.0 == 0.0
+    }
+
     #[inline]
     pub(crate) fn sq(self) -> f64 {
         self.0 * self.0
     }
 }
 
+impl Mul<f64> for Volatility {
+    type Output = Volatility;
+
+    #[inline]
+    fn mul(self, scalar: f64) -> Volatility {
+        Volatility(self.0 * scalar)
+    }
+}
+
+impl Mul<Volatility> for f64 {
+    type Output = Volatility;
+
+    #[inline]
+    fn mul(self, Volatility(volatility): Volatility) -> Volatility {
+        Volatility(self * volatility)
+    }
+}
+
+impl MulAssign<f64> for Volatility {
+    #[inline]
+    fn mul_assign(&mut self, scalar: f64) {
+        self.0 *= scalar;
+    }
+}
+
 impl From<Volatility> for f64 {
     #[inline]
     fn from(Volatility(volatility): Volatility) -> f64 {
@@ -235,3 +304,246 @@ pub struct Rating {
     /// Point in time at which the rating was last updated.
     pub at: Instant,
 }
+
+impl Rating {
+    /// Default number of deviations subtracted from the rating in
+    /// [`Rating::seeding_score()`].
+    pub const DEFAULT_SEEDING_DEVIATIONS: f64 = 2.0;
+
+    /// A conservative estimate of playing strength, that can be used to
+    /// compare players for seeding: The rating minus
+    /// [`Rating::DEFAULT_SEEDING_DEVIATIONS`] times the deviation, i.e., the
+    /// lower end of the approximate 95% confidence interval.
+    ///
+    /// Players with uncertain ratings are seeded lower than established
+    /// players with the same rating, without needing an arbitrary cutoff.
+    ///
+    /// See [`RatingSystem::seeding_score()`] for a configurable variant.
+    pub fn seeding_score(&self) -> f64 {
+        f64::from(self.rating - Rating::DEFAULT_SEEDING_DEVIATIONS * self.deviation)
+    }
+
+    /// The deviation interpreted as a Glicko-1 rating deviation (RD).
+    ///
+    /// Glicko-2 deviations on the external scale are numerically the same as
+    /// Glicko-1 RDs, so this is just the deviation as a plain number.
+    pub fn glicko1_rd(&self) -> f64 {
+        f64::from(self.deviation)
+    }
+
+    /// The approximate 95% confidence interval in the style of Glicko-1,
+    /// from the rating minus twice the RD to the rating plus twice the RD.
+    pub fn glicko1_interval(&self) -> (RatingScalar, RatingScalar) {
+        (
+            self.rating - 2.0 * self.deviation,
+            self.rating + 2.0 * self.deviation,
+        )
+    }
+
+    /// Convert to `(mu, phi, sigma)` on the internal Glicko-2 scale used by
+    /// the rating system, as named in the Glicko-2 paper. `mu` is
+    /// centered around the default rating of the system.
+    ///
+    /// Useful to compare intermediate values with reference
+    /// implementations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, Rating, RatingDifference, RatingScalar, RatingSystem, Volatility};
+    ///
+    /// let system = RatingSystem::new();
+    ///
+    /// // Example player from the Glicko-2 paper.
+    /// let rating = Rating {
+    ///     rating: RatingScalar(1500.0),
+    ///     deviation: RatingDifference(200.0),
+    ///     volatility: Volatility(0.06),
+    ///     at: Instant(0.0),
+    /// };
+    ///
+    /// let (mu, phi, sigma) = rating.to_internal_components(&system);
+    /// assert_eq!(mu, 0.0);
+    /// assert!((phi - 1.1513).abs() < 1e-4);
+    /// assert_eq!(sigma, 0.06);
+    ///
+    /// let roundtrip = Rating::from_internal_components(mu, phi, sigma, rating.at, &system);
+    /// assert!((f64::from(roundtrip.deviation) - 200.0).abs() < 1e-9);
+    /// ```
+    pub fn to_internal_components(&self, system: &RatingSystem) -> (f64, f64, f64) {
+        let scale = system.internal_rating_scale();
+        (
+            (self.rating - system.default_rating()).to_internal(scale).0,
+            self.deviation.to_internal(scale).0,
+            self.volatility.0,
+        )
+    }
+
+    /// Construct a rating from `(mu, phi, sigma)` on the internal Glicko-2
+    /// scale used by the rating system. Inverse of
+    /// [`Rating::to_internal_components()`].
+    pub fn from_internal_components(
+        mu: f64,
+        phi: f64,
+        sigma: f64,
+        at: Instant,
+        system: &RatingSystem,
+    ) -> Rating {
+        let scale = system.internal_rating_scale();
+        Rating {
+            rating: system.default_rating() + InternalRatingDifference(mu).to_external(scale),
+            deviation: InternalRatingDifference(phi).to_external(scale),
+            volatility: Volatility(sigma),
+            at,
+        }
+    }
+
+    /// Average of the given ratings, weighted by their precision
+    /// `1 / deviation²`. Returns `None` if there are no ratings.
+    ///
+    /// Uncertain ratings contribute less, without needing a hard cutoff.
+    /// Deviations are taken as they are, so preview them at a common point in
+    /// time first, if needed.
+    ///
+    /// Ratings with a deviation of zero (or so small that the precision
+    /// overflows) have infinite precision. If there are any, the result is
+    /// the plain average of just those ratings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, Rating, RatingDifference, RatingScalar, Volatility};
+    ///
+    /// let established = Rating {
+    ///     rating: RatingScalar(2000.0),
+    ///     deviation: RatingDifference(50.0),
+    ///     volatility: Volatility(0.06),
+    ///     at: Instant(0.0),
+    /// };
+    /// let uncertain = Rating {
+    ///     rating: RatingScalar(1000.0),
+    ///     deviation: RatingDifference(500.0),
+    ///     ..established.clone()
+    /// };
+    ///
+    /// let mean = Rating::precision_weighted_mean([established.clone(), uncertain.clone()]).unwrap();
+    /// assert!(mean > RatingScalar(1980.0));
+    ///
+    /// let exact = Rating {
+    ///     rating: RatingScalar(1800.0),
+    ///     deviation: RatingDifference(0.0),
+    ///     ..established.clone()
+    /// };
+    /// let mean = Rating::precision_weighted_mean([established, uncertain, exact]).unwrap();
+    /// assert_eq!(mean, RatingScalar(1800.0));
+    /// ```
+    pub fn precision_weighted_mean(
+        ratings: impl IntoIterator<Item = Rating>,
+    ) -> Option<RatingScalar> {
+        let mut total_weight = 0.0;
+        let mut total = 0.0;
+        let mut exact_count = 0;
+        let mut exact_total = 0.0;
+        let mut any = false;
+
+        for rating in ratings {
+            let weight = 1.0 / rating.deviation.0.powi(2);
+            if weight.is_infinite() {
+                exact_count += 1;
+                exact_total += rating.rating.0;
+            } else {
+                total_weight += weight;
+                total += weight * rating.rating.0;
+            }
+            any = true;
+        }
+
+        any.then(|| {
+            if exact_count > 0 {
+                RatingScalar(exact_total / f64::from(exact_count))
+            } else {
+                RatingScalar(total / total_weight)
+            }
+        })
+    }
+}
+
+/// Compact storage for a [`Rating`], using single precision floats.
+///
+/// Takes half the memory of a [`Rating`], which can speed up batch
+/// processing of many ratings, where memory bandwidth dominates.
+///
+/// Only the storage uses single precision. Rating updates are still computed
+/// in double precision, by converting to and from [`Rating`]. An update in
+/// single precision is not provided, because the iterative volatility update
+/// needs more precision than `f32` offers to reliably converge with the
+/// default [`RatingSystemBuilder::convergence_tolerance()`](crate::RatingSystemBuilder::convergence_tolerance).
+///
+/// Every conversion from a [`Rating`] rounds each component to about 7
+/// significant digits, i.e., to the nearest multiple of about `0.0001`
+/// rating points for typical ratings. The instant is rounded relative to
+/// its distance from `Instant(0.0)`: For instants thousands of rating
+/// periods after that, like the ones derived from Unix timestamps in the
+/// research binaries, the spacing is about `0.0005` rating periods, i.e.,
+/// minutes. Rounding happens again whenever an updated rating is stored, so
+/// the errors accumulate over a long replay.
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{Instant, Rating, Rating32, RatingSystem, Score};
+///
+/// let system = RatingSystem::new();
+/// let mut a = system.new_rating();
+/// let mut b = system.new_rating();
+/// let mut a32 = Rating32::from(&a);
+/// let mut b32 = Rating32::from(&b);
+///
+/// for i in 0..1000 {
+///     let now = Instant(f64::from(i) * 0.1);
+///     let score = if i % 3 == 0 { Score::LOSS } else { Score::WIN };
+///
+///     (a, b) = system.update_ratings(&a, &b, score, now).unwrap();
+///
+///     let (new_a, new_b) = system.update_ratings(&a32.into(), &b32.into(), score, now).unwrap();
+///     (a32, b32) = (Rating32::from(&new_a), Rating32::from(&new_b));
+/// }
+///
+/// assert!((Rating::from(a32).rating - a.rating).abs().0 < 0.1);
+/// assert!((Rating::from(b32).deviation - b.deviation).abs().0 < 0.1);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Rating32 {
+    pub rating: f32,
+    pub deviation: f32,
+    pub volatility: f32,
+    pub at: f32,
+}
+
+impl From<&Rating> for Rating32 {
+    fn from(rating: &Rating) -> Rating32 {
+        Rating32 {
+            rating: rating.rating.0 as f32,
+            deviation: rating.deviation.0 as f32,
+            volatility: rating.volatility.0 as f32,
+            at: rating.at.0 as f32,
+        }
+    }
+}
+
+impl From<Rating> for Rating32 {
+    fn from(rating: Rating) -> Rating32 {
+        Rating32::from(&rating)
+    }
+}
+
+impl From<Rating32> for Rating {
+    fn from(rating: Rating32) -> Rating {
+        Rating {
+            rating: RatingScalar(f64::from(rating.rating)),
+            deviation: RatingDifference(f64::from(rating.deviation)),
+            volatility: Volatility(f64::from(rating.volatility)),
+            at: Instant(f64::from(rating.at)),
+        }
+    }
+}
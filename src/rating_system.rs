@@ -1,7 +1,10 @@
 use std::{error::Error, f64::consts::PI, fmt};
 
 use crate::{
-    internal_rating::InternalRatingDifference,
+    dedup::DedupSet,
+    expected_deviance,
+    internal_rating::{InternalRatingDifference, DEFAULT_INTERNAL_RATING_SCALE},
+    metrics::{deviance_gradient_wrt_logit, Attribution},
     rating::{Rating, RatingDifference, RatingScalar, Volatility},
     Instant, Periods, Score,
 };
@@ -31,6 +34,7 @@ pub struct RatingSystemBuilder {
 
     min_deviation: RatingDifference,
     max_deviation: RatingDifference,
+    default_deviation: Option<RatingDifference>,
 
     first_advantage: RatingDifference,
 
@@ -42,6 +46,28 @@ pub struct RatingSystemBuilder {
     max_rating_delta: RatingDifference,
 
     regulator_factor: f64,
+
+    regulator_mode: RegulatorMode,
+
+    margin_scale: f64,
+
+    internal_rating_scale: f64,
+
+    discrete_periods: bool,
+
+    seeding_deviations: f64,
+
+    max_volatility_increase_per_game: f64,
+
+    discount_unestablished_opponent: Option<(RatingDifference, f64)>,
+
+    volatility_discounts_opponent_deviation: bool,
+
+    provisional_deviation: RatingDifference,
+
+    unbounded_rating: bool,
+
+    display_smoothing: f64,
 }
 
 impl RatingSystemBuilder {
@@ -100,6 +126,14 @@ impl RatingSystemBuilder {
         self
     }
 
+    /// Set the deviation of new players. It will be clamped to the range of
+    /// allowed deviations. The default is the maximum deviation.
+    pub fn default_deviation(&mut self, default_deviation: RatingDifference) -> &mut Self {
+        assert!(default_deviation >= RatingDifference(0.0));
+        self.default_deviation = Some(default_deviation);
+        self
+    }
+
     /// Set the inherent advantage for the first player. The default is `0.0`.
     pub fn first_advantage(&mut self, first_advantage: RatingDifference) -> &mut Self {
         self.first_advantage = first_advantage;
@@ -146,6 +180,280 @@ impl RatingSystemBuilder {
         self
     }
 
+    /// Set how the regulator factor is applied. The default is
+    /// [`RegulatorMode::AsymmetricGain`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingSystem, RegulatorMode, Score};
+    ///
+    /// let system = RatingSystem::builder()
+    ///     .regulator_mode(RegulatorMode::Symmetric)
+    ///     .build();
+    ///
+    /// let first = system.new_rating();
+    /// let second = system.new_rating();
+    /// let (first_after, second_after) = system
+    ///     .update_ratings(&first, &second, Score::WIN, Instant(0.0))
+    ///     .unwrap();
+    ///
+    /// let first_delta = first_after.rating - first.rating;
+    /// let second_delta = second_after.rating - second.rating;
+    /// assert!(f64::abs((first_delta + second_delta).0) < 1e-9);
+    /// ```
+    pub fn regulator_mode(&mut self, regulator_mode: RegulatorMode) -> &mut Self {
+        self.regulator_mode = regulator_mode;
+        self
+    }
+
+    /// Set the margin of victory that is considered fairly decisive in
+    /// [`RatingSystem::update_ratings_margin()`]. A margin of this size maps
+    /// to an effective score of about `0.88`. The default is `1.0`.
+    pub fn margin_scale(&mut self, margin_scale: f64) -> &mut Self {
+        assert!(margin_scale > 0.0);
+        self.margin_scale = margin_scale;
+        self
+    }
+
+    /// Set the number of rating points that correspond to one unit on the
+    /// internal Glicko-2 scale. The default is `173.7178`.
+    ///
+    /// Changing the scale effectively changes how rating differences map to
+    /// win probabilities. For example, doubling the scale means that rating
+    /// differences (and deviations) need to be twice as large to make the
+    /// same prediction.
+    pub fn internal_rating_scale(&mut self, internal_rating_scale: f64) -> &mut Self {
+        assert!(internal_rating_scale > 0.0);
+        self.internal_rating_scale = internal_rating_scale;
+        self
+    }
+
+    /// Set whether rating deviations decay only for complete rating periods,
+    /// like in the original Glicko-2 system, instead of continuously with
+    /// fractional rating periods. The default is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, Periods, RatingSystem};
+    ///
+    /// let system = RatingSystem::builder().discrete_periods(true).build();
+    /// let mut rating = system.new_rating();
+    /// rating.deviation = system.min_deviation();
+    ///
+    /// let almost = system.preview_deviation(&rating, rating.at + Periods(0.9));
+    /// assert_eq!(almost, rating.deviation);
+    ///
+    /// let full = system.preview_deviation(&rating, rating.at + Periods(1.0));
+    /// assert!(full > rating.deviation);
+    /// ```
+    pub fn discrete_periods(&mut self, discrete_periods: bool) -> &mut Self {
+        self.discrete_periods = discrete_periods;
+        self
+    }
+
+    /// Set the number of deviations that are subtracted from ratings in
+    /// [`RatingSystem::seeding_score()`]. The default is `2.0`.
+    pub fn seeding_deviations(&mut self, seeding_deviations: f64) -> &mut Self {
+        assert!(seeding_deviations >= 0.0);
+        self.seeding_deviations = seeding_deviations;
+        self
+    }
+
+    /// Set by how much a single game can increase the volatility of a
+    /// rating. The default is unlimited.
+    ///
+    /// This stabilizes ratings after an upset, which would otherwise spike
+    /// the volatility and cause large swings in subsequent games. It is
+    /// independent of the upper bound set by
+    /// [`RatingSystemBuilder::max_volatility()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, Rating, RatingDifference, RatingScalar, RatingSystem, Score, Volatility};
+    ///
+    /// let established = Rating {
+    ///     rating: RatingScalar(2000.0),
+    ///     deviation: RatingDifference(50.0),
+    ///     volatility: Volatility(0.06),
+    ///     at: Instant(0.0),
+    /// };
+    /// let underdog = Rating {
+    ///     rating: RatingScalar(1200.0),
+    ///     ..established.clone()
+    /// };
+    ///
+    /// let unlimited = RatingSystem::new();
+    /// let limited = RatingSystem::builder()
+    ///     .max_volatility_increase_per_game(0.00001)
+    ///     .build();
+    ///
+    /// let (spiked, _) = unlimited.update_ratings(&established, &underdog, Score::LOSS, Instant(0.0)).unwrap();
+    /// let (damped, _) = limited.update_ratings(&established, &underdog, Score::LOSS, Instant(0.0)).unwrap();
+    /// assert!(damped.volatility <= Volatility(0.06 + 0.00001));
+    /// assert!(damped.volatility < spiked.volatility);
+    ///
+    /// // The next game causes a smaller swing.
+    /// let now = Instant(1.0);
+    /// let (after_spiked, _) = unlimited.update_ratings(&spiked, &established, Score::WIN, now).unwrap();
+    /// let (after_damped, _) = limited.update_ratings(&damped, &established, Score::WIN, now).unwrap();
+    /// assert!(after_damped.rating - damped.rating < after_spiked.rating - spiked.rating);
+    /// ```
+    pub fn max_volatility_increase_per_game(
+        &mut self,
+        max_volatility_increase_per_game: f64,
+    ) -> &mut Self {
+        assert!(max_volatility_increase_per_game >= 0.0);
+        self.max_volatility_increase_per_game = max_volatility_increase_per_game;
+        self
+    }
+
+    /// Scale rating losses by `factor`, when an established player (with a
+    /// deviation of at most `threshold`) loses against an opponent whose
+    /// deviation exceeds `threshold`. The default is to not discount any
+    /// losses.
+    ///
+    /// Wins against unestablished opponents already barely move ratings,
+    /// but losses against them can be costly while their rating is still
+    /// being calibrated. This only affects losses of the established side,
+    /// not gains, so the system is no longer symmetric and total rating
+    /// slowly inflates when new players enter the pool.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, Rating, RatingDifference, RatingScalar, RatingSystem, Score, Volatility};
+    ///
+    /// let system = RatingSystem::builder()
+    ///     .discount_unestablished_opponent(RatingDifference(250.0), 0.5)
+    ///     .build();
+    ///
+    /// let established = Rating {
+    ///     rating: RatingScalar(1500.0),
+    ///     deviation: RatingDifference(60.0),
+    ///     volatility: Volatility(0.06),
+    ///     at: Instant(0.0),
+    /// };
+    /// let newcomer = system.new_rating();
+    ///
+    /// let (discounted, _) = system.update_ratings(&established, &newcomer, Score::LOSS, Instant(0.0)).unwrap();
+    /// let (full, _) = RatingSystem::new().update_ratings(&established, &newcomer, Score::LOSS, Instant(0.0)).unwrap();
+    /// assert!(discounted.rating > full.rating);
+    /// assert!(discounted.rating < established.rating);
+    ///
+    /// // Not discounted between newcomers.
+    /// let (discounted, _) = system.update_ratings(&newcomer, &newcomer, Score::LOSS, Instant(0.0)).unwrap();
+    /// let (full, _) = RatingSystem::new().update_ratings(&newcomer, &newcomer, Score::LOSS, Instant(0.0)).unwrap();
+    /// assert_eq!(discounted.rating, full.rating);
+    /// ```
+    pub fn discount_unestablished_opponent(
+        &mut self,
+        threshold: RatingDifference,
+        factor: f64,
+    ) -> &mut Self {
+        assert!(threshold >= RatingDifference(0.0));
+        assert!(factor >= 0.0);
+        self.discount_unestablished_opponent = Some((threshold, factor));
+        self
+    }
+
+    /// Set whether the surprise of a result, which drives the volatility
+    /// update, is scaled by the reliability of the opponent rating. The
+    /// default is `false`, which leaves the algorithm unchanged.
+    ///
+    /// A surprising result against an opponent with an uncertain rating says
+    /// little about the consistency of our own performance, so it should not
+    /// raise our volatility as much. The reliability is the factor `g` of
+    /// the previewed opponent deviation, between `0.0` and `1.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, Rating, RatingDifference, RatingScalar, RatingSystem, Score, Volatility};
+    ///
+    /// let discounting = RatingSystem::builder()
+    ///     .volatility_discounts_opponent_deviation(true)
+    ///     .build();
+    ///
+    /// let established = Rating {
+    ///     rating: RatingScalar(2000.0),
+    ///     deviation: RatingDifference(50.0),
+    ///     volatility: Volatility(0.06),
+    ///     at: Instant(0.0),
+    /// };
+    /// let newcomer = Rating {
+    ///     rating: RatingScalar(1200.0),
+    ///     deviation: RatingDifference(400.0),
+    ///     ..established.clone()
+    /// };
+    ///
+    /// let (swing, _) = RatingSystem::new().update_ratings(&established, &newcomer, Score::LOSS, Instant(0.0)).unwrap();
+    /// let (damped, _) = discounting.update_ratings(&established, &newcomer, Score::LOSS, Instant(0.0)).unwrap();
+    /// assert!(damped.volatility < swing.volatility);
+    /// ```
+    pub fn volatility_discounts_opponent_deviation(
+        &mut self,
+        volatility_discounts_opponent_deviation: bool,
+    ) -> &mut Self {
+        self.volatility_discounts_opponent_deviation = volatility_discounts_opponent_deviation;
+        self
+    }
+
+    /// Set the deviation above which ratings are considered provisional. The
+    /// default is `110.0`, like on Lichess.
+    pub fn provisional_deviation(&mut self, provisional_deviation: RatingDifference) -> &mut Self {
+        assert!(provisional_deviation >= RatingDifference(0.0));
+        self.provisional_deviation = provisional_deviation;
+        self
+    }
+
+    /// Disable limiting rating values to the range between
+    /// [`RatingSystemBuilder::min_rating()`] and
+    /// [`RatingSystemBuilder::max_rating()`], which are then ignored.
+    /// Deviations and volatilities are still limited. Intended for research.
+    ///
+    /// This waives the guarantees for pathological inputs: Extreme (but
+    /// finite) ratings are processed as they are, and infinite ratings are
+    /// not brought back into a finite range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::builder().unbounded_rating().build();
+    ///
+    /// let mut strong = system.new_rating();
+    /// strong.rating = RatingScalar(1e6);
+    /// let mut weak = system.new_rating();
+    /// weak.rating = RatingScalar(-1e6);
+    ///
+    /// for (i, score) in [Score::WIN, Score::DRAW, Score::LOSS].into_iter().cycle().take(30).enumerate() {
+    ///     let now = Instant(i as f64);
+    ///     assert!(!system.expected_score(&strong, &weak, now).value().is_nan());
+    ///     (strong, weak) = system.update_ratings(&strong, &weak, score, now).unwrap();
+    ///     assert!(strong.rating.0.is_finite() && weak.rating.0.is_finite());
+    /// }
+    ///
+    /// assert!(strong.rating > RatingScalar(4000.0));
+    /// assert!(weak.rating < RatingScalar(400.0));
+    /// ```
+    pub fn unbounded_rating(&mut self) -> &mut Self {
+        self.unbounded_rating = true;
+        self
+    }
+
+    /// Set how much of the previously displayed rating value is retained in
+    /// [`RatingSystem::display_value()`], in the range `0.0..1.0`. With `0.0`,
+    /// the raw rating value is displayed. The default is `0.0`.
+    pub fn display_smoothing(&mut self, display_smoothing: f64) -> &mut Self {
+        assert!((0.0..1.0).contains(&display_smoothing));
+        self.display_smoothing = display_smoothing;
+        self
+    }
+
     pub fn build(&self) -> RatingSystem {
         assert!(self.min_rating <= self.max_rating);
         assert!(self.min_deviation <= self.max_deviation);
@@ -162,6 +470,7 @@ impl RatingSystemBuilder {
 
             min_deviation: self.min_deviation,
             max_deviation: self.max_deviation,
+            default_deviation: self.default_deviation,
 
             first_advantage: self.first_advantage,
 
@@ -173,6 +482,28 @@ impl RatingSystemBuilder {
             max_rating_delta: self.max_rating_delta,
 
             regulator_factor: self.regulator_factor,
+
+            regulator_mode: self.regulator_mode,
+
+            margin_scale: self.margin_scale,
+
+            internal_rating_scale: self.internal_rating_scale,
+
+            discrete_periods: self.discrete_periods,
+
+            seeding_deviations: self.seeding_deviations,
+
+            max_volatility_increase_per_game: self.max_volatility_increase_per_game,
+
+            discount_unestablished_opponent: self.discount_unestablished_opponent,
+
+            volatility_discounts_opponent_deviation: self.volatility_discounts_opponent_deviation,
+
+            provisional_deviation: self.provisional_deviation,
+
+            unbounded_rating: self.unbounded_rating,
+
+            display_smoothing: self.display_smoothing,
         }
     }
 }
@@ -195,6 +526,7 @@ pub struct RatingSystem {
 
     min_deviation: RatingDifference,
     max_deviation: RatingDifference,
+    default_deviation: Option<RatingDifference>,
 
     first_advantage: RatingDifference,
 
@@ -206,6 +538,28 @@ pub struct RatingSystem {
     max_rating_delta: RatingDifference,
 
     regulator_factor: f64,
+
+    regulator_mode: RegulatorMode,
+
+    margin_scale: f64,
+
+    internal_rating_scale: f64,
+
+    discrete_periods: bool,
+
+    seeding_deviations: f64,
+
+    max_volatility_increase_per_game: f64,
+
+    discount_unestablished_opponent: Option<(RatingDifference, f64)>,
+
+    volatility_discounts_opponent_deviation: bool,
+
+    provisional_deviation: RatingDifference,
+
+    unbounded_rating: bool,
+
+    display_smoothing: f64,
 }
 
 impl Default for RatingSystem {
@@ -232,6 +586,7 @@ impl RatingSystem {
 
             min_deviation: RatingDifference(45.0),
             max_deviation: RatingDifference(500.0),
+            default_deviation: None,
 
             first_advantage: RatingDifference(0.0),
 
@@ -243,6 +598,28 @@ impl RatingSystem {
             max_rating_delta: RatingDifference(700.0),
 
             regulator_factor: 1.02,
+
+            regulator_mode: RegulatorMode::AsymmetricGain,
+
+            margin_scale: 1.0,
+
+            internal_rating_scale: DEFAULT_INTERNAL_RATING_SCALE,
+
+            discrete_periods: false,
+
+            seeding_deviations: Rating::DEFAULT_SEEDING_DEVIATIONS,
+
+            max_volatility_increase_per_game: f64::INFINITY,
+
+            discount_unestablished_opponent: None,
+
+            volatility_discounts_opponent_deviation: false,
+
+            provisional_deviation: RatingDifference(110.0),
+
+            unbounded_rating: false,
+
+            display_smoothing: 0.0,
         }
     }
 
@@ -250,6 +627,68 @@ impl RatingSystem {
         RatingSystem::builder().build()
     }
 
+    /// Create a builder with the parameters of this rating system, to build
+    /// a modified rating system.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingDifference, RatingSystem};
+    ///
+    /// let system = RatingSystem::builder().tau(0.5).build();
+    /// let modified = system.to_builder().first_advantage(RatingDifference(10.0)).build();
+    /// assert_eq!(modified.tau(), 0.5);
+    /// assert_eq!(modified.first_advantage(), RatingDifference(10.0));
+    /// ```
+    pub fn to_builder(&self) -> RatingSystemBuilder {
+        RatingSystemBuilder {
+            min_rating: self.min_rating,
+            max_rating: self.max_rating,
+            default_rating: self.default_rating,
+
+            min_volatility: self.min_volatility,
+            max_volatility: self.max_volatility,
+            default_volatility: self.default_volatility,
+
+            min_deviation: self.min_deviation,
+            max_deviation: self.max_deviation,
+            default_deviation: self.default_deviation,
+
+            first_advantage: self.first_advantage,
+
+            tau: self.tau,
+
+            convergence_tolerance: self.convergence_tolerance,
+            max_convergence_iterations: self.max_convergence_iterations,
+
+            max_rating_delta: self.max_rating_delta,
+
+            regulator_factor: self.regulator_factor,
+
+            regulator_mode: self.regulator_mode,
+
+            margin_scale: self.margin_scale,
+
+            internal_rating_scale: self.internal_rating_scale,
+
+            discrete_periods: self.discrete_periods,
+
+            seeding_deviations: self.seeding_deviations,
+
+            max_volatility_increase_per_game: self.max_volatility_increase_per_game,
+
+            discount_unestablished_opponent: self.discount_unestablished_opponent,
+
+            volatility_discounts_opponent_deviation: self.volatility_discounts_opponent_deviation,
+
+            provisional_deviation: self.provisional_deviation,
+
+            unbounded_rating: self.unbounded_rating,
+
+            display_smoothing: self.display_smoothing,
+        }
+    }
+
     pub fn min_rating(&self) -> RatingScalar {
         self.min_rating
     }
@@ -282,6 +721,12 @@ impl RatingSystem {
         self.max_deviation
     }
 
+    pub fn default_deviation(&self) -> RatingDifference {
+        self.default_deviation
+            .unwrap_or(self.max_deviation)
+            .clamp(self.min_deviation, self.max_deviation)
+    }
+
     pub fn first_advantage(&self) -> RatingDifference {
         self.first_advantage
     }
@@ -306,11 +751,169 @@ impl RatingSystem {
         self.regulator_factor
     }
 
+    pub fn regulator_mode(&self) -> RegulatorMode {
+        self.regulator_mode
+    }
+
+    pub fn margin_scale(&self) -> f64 {
+        self.margin_scale
+    }
+
+    pub fn internal_rating_scale(&self) -> f64 {
+        self.internal_rating_scale
+    }
+
+    pub fn discrete_periods(&self) -> bool {
+        self.discrete_periods
+    }
+
+    pub fn seeding_deviations(&self) -> f64 {
+        self.seeding_deviations
+    }
+
+    pub fn max_volatility_increase_per_game(&self) -> f64 {
+        self.max_volatility_increase_per_game
+    }
+
+    pub fn discount_unestablished_opponent(&self) -> Option<(RatingDifference, f64)> {
+        self.discount_unestablished_opponent
+    }
+
+    pub fn volatility_discounts_opponent_deviation(&self) -> bool {
+        self.volatility_discounts_opponent_deviation
+    }
+
+    pub fn unbounded_rating(&self) -> bool {
+        self.unbounded_rating
+    }
+
+    pub fn display_smoothing(&self) -> f64 {
+        self.display_smoothing
+    }
+
+    pub fn provisional_deviation(&self) -> RatingDifference {
+        self.provisional_deviation
+    }
+
+    /// Version of the rating update algorithm. It is bumped whenever the
+    /// math changes, such that identical parameters can produce different
+    /// ratings.
+    pub fn algorithm_version() -> u32 {
+        1
+    }
+
+    /// Hash of the algorithm version and all parameters of the rating
+    /// system that affect rating updates. Can be stored alongside persisted
+    /// ratings, to detect when they were produced by a different
+    /// configuration and need to be recomputed.
+    ///
+    /// Parameters that only affect the presentation or classification of
+    /// ratings, like [`RatingSystemBuilder::display_smoothing()`],
+    /// [`RatingSystemBuilder::provisional_deviation()`] and
+    /// [`RatingSystemBuilder::seeding_deviations()`], are not included.
+    ///
+    /// The hash is stable across platforms, but may change between releases
+    /// of this crate, whenever parameters are added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingDifference, RatingSystem};
+    ///
+    /// let default = RatingSystem::new();
+    /// let custom = RatingSystem::builder().tau(0.5).build();
+    /// assert_eq!(default.fingerprint(), RatingSystem::new().fingerprint());
+    /// assert_ne!(default.fingerprint(), custom.fingerprint());
+    ///
+    /// let smoothed = RatingSystem::builder().display_smoothing(0.5).build();
+    /// assert_eq!(default.fingerprint(), smoothed.fingerprint());
+    ///
+    /// let strict = RatingSystem::builder().provisional_deviation(RatingDifference(80.0)).build();
+    /// assert_eq!(default.fingerprint(), strict.fingerprint());
+    ///
+    /// let seeded = RatingSystem::builder().seeding_deviations(3.0).build();
+    /// assert_eq!(default.fingerprint(), seeded.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let (discount_threshold, discount_factor) = self
+            .discount_unestablished_opponent
+            .map_or((f64::NAN, f64::NAN), |(threshold, factor)| {
+                (threshold.0, factor)
+            });
+
+        // Remember to update if parameters that affect updates are added.
+        let words = [
+            u64::from(RatingSystem::algorithm_version()),
+            self.min_rating.0.to_bits(),
+            self.max_rating.0.to_bits(),
+            self.default_rating.0.to_bits(),
+            self.min_volatility.0.to_bits(),
+            self.max_volatility.0.to_bits(),
+            self.default_volatility.0.to_bits(),
+            self.min_deviation.0.to_bits(),
+            self.max_deviation.0.to_bits(),
+            self.default_deviation().0.to_bits(),
+            self.first_advantage.0.to_bits(),
+            self.tau.to_bits(),
+            self.convergence_tolerance.to_bits(),
+            u64::from(self.max_convergence_iterations),
+            self.max_rating_delta.0.to_bits(),
+            self.regulator_factor.to_bits(),
+            self.regulator_mode as u64,
+            self.margin_scale.to_bits(),
+            self.internal_rating_scale.to_bits(),
+            u64::from(self.discrete_periods),
+            self.max_volatility_increase_per_game.to_bits(),
+            discount_threshold.to_bits(),
+            discount_factor.to_bits(),
+            u64::from(self.volatility_discounts_opponent_deviation),
+            u64::from(self.unbounded_rating),
+        ];
+
+        // FNV-1a
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for word in words {
+            for byte in word.to_le_bytes() {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        }
+        hash
+    }
+
+    /// Command line arguments for the research binaries, reproducing the
+    /// parameters of this rating system that they support.
+    ///
+    /// The rating periods per day are not part of the rating system, and must
+    /// be added separately.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::RatingSystem;
+    ///
+    /// let args = RatingSystem::new().to_cli_args();
+    /// assert_eq!(args.join(" "), "--min-deviation 45 --max-deviation 500 --default-volatility 0.09 --tau 0.75 --first-advantage 0 --regulator-factor 1.02");
+    /// ```
+    pub fn to_cli_args(&self) -> Vec<String> {
+        [
+            ("--min-deviation", self.min_deviation.0),
+            ("--max-deviation", self.max_deviation.0),
+            ("--default-volatility", self.default_volatility.0),
+            ("--tau", self.tau),
+            ("--first-advantage", self.first_advantage.0),
+            ("--regulator-factor", self.regulator_factor),
+        ]
+        .into_iter()
+        .flat_map(|(flag, value)| [flag.to_owned(), value.to_string()])
+        .collect()
+    }
+
     /// Construct an initial rating for a new player.
     pub fn new_rating(&self) -> Rating {
         Rating {
-            rating: self.default_rating.clamp(self.min_rating, self.max_rating),
-            deviation: self.max_deviation,
+            rating: self.clamp_rating_value(self.default_rating),
+            deviation: self.default_deviation(),
             volatility: self
                 .default_volatility
                 .clamp(self.min_volatility, self.max_volatility),
@@ -318,88 +921,2781 @@ impl RatingSystem {
         }
     }
 
+    /// Construct an initial rating for a new player, starting near a rough
+    /// estimate of their strength, like a self-reported skill level.
+    ///
+    /// The deviation is interpolated linearly from the maximum deviation (at
+    /// `confidence = 0.0`) to the minimum deviation (at `confidence = 1.0`),
+    /// with `confidence` limited to that range.
+    /// The volatility starts at the default volatility.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    ///
+    /// let rating = system.new_rating_from_estimate(RatingScalar(1800.0), 0.0);
+    /// assert_eq!(rating.rating, RatingScalar(1800.0));
+    /// assert_eq!(rating.deviation, system.max_deviation());
+    ///
+    /// let rating = system.new_rating_from_estimate(RatingScalar(1800.0), 1.0);
+    /// assert_eq!(rating.deviation, system.min_deviation());
+    /// ```
+    pub fn new_rating_from_estimate(&self, estimate: RatingScalar, confidence: f64) -> Rating {
+        let confidence = confidence.clamp(0.0, 1.0);
+
+        Rating {
+            rating: self.clamp_rating_value(estimate),
+            deviation: self.max_deviation + confidence * (self.min_deviation - self.max_deviation),
+            ..self.new_rating()
+        }
+    }
+
+    /// Shift all ratings by the same amount, such that their mean is
+    /// `target_mean`.
+    ///
+    /// This changes absolute ratings, but preserves all rating differences,
+    /// and therefore all predictions. It can be used to periodically undo
+    /// inflation caused by the regulator in a closed pool, without distorting
+    /// the ladder. Does nothing if there are no ratings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut ratings = [system.new_rating(), system.new_rating()];
+    /// ratings[0].rating = RatingScalar(1600.0);
+    /// ratings[1].rating = RatingScalar(1800.0);
+    ///
+    /// RatingSystem::recenter(&mut ratings, RatingScalar(1500.0));
+    /// assert_eq!(ratings[0].rating, RatingScalar(1400.0));
+    /// assert_eq!(ratings[1].rating, RatingScalar(1600.0));
+    /// ```
+    pub fn recenter(ratings: &mut [Rating], target_mean: RatingScalar) {
+        if ratings.is_empty() {
+            return;
+        }
+
+        let mean = ratings.iter().map(|rating| rating.rating.0).sum::<f64>() / ratings.len() as f64;
+        let shift = target_mean - RatingScalar(mean);
+
+        for rating in ratings {
+            rating.rating += shift;
+        }
+    }
+
+    /// Like [`Rating::seeding_score()`], but subtracting the configured number
+    /// of deviations from the rating, after clamping it to the allowed
+    /// ranges.
+    pub fn seeding_score(&self, rating: &Rating) -> f64 {
+        let rating = self.clamp_rating(rating);
+        f64::from(rating.rating - self.seeding_deviations * rating.deviation)
+    }
+
     /// Preview the rating deviation that a player will have at a future
     /// point in time if no games are played until then.
     pub fn preview_deviation(&self, rating: &Rating, at: Instant) -> RatingDifference {
         let rating = self.clamp_rating(rating);
 
         new_deviation(
-            rating.deviation.to_internal(),
+            rating.deviation.to_internal(self.internal_rating_scale),
             rating.volatility,
-            at.elapsed_since(rating.at),
+            self.decay_periods(at.elapsed_since(rating.at)),
         )
-        .to_external()
+        .to_external(self.internal_rating_scale)
         .clamp(self.min_deviation, self.max_deviation)
     }
 
-    /// Calculate the expected score for the first player in a game against the
-    /// second player.
-    pub fn expected_score(&self, first: &Rating, second: &Rating, now: Instant) -> Score {
-        let first = self.clamp_rating(first);
-        let second = self.clamp_rating(second);
-
-        expectation_value(
-            (first.rating - second.rating + self.first_advantage).to_internal(),
-            g(InternalRatingDifference::hypot(
-                self.preview_deviation(&first, now).to_internal(),
-                self.preview_deviation(&second, now).to_internal(),
-            )),
-        )
+    /// The deviation that is used as `phi` in the rating update at `now`, on
+    /// the external scale.
+    ///
+    /// Unlike the original Glicko-2 system, Lichess previews the deviation
+    /// one rating period before `now` (step 2), so that a game played right
+    /// after the previous one does not decay the deviation at all. The
+    /// decay for the elapsed time is instead applied after computing the
+    /// new volatility (step 6).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, Periods, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut rating = system.new_rating();
+    /// rating.deviation = system.min_deviation();
+    ///
+    /// let now = rating.at + Periods(5.0);
+    /// assert_eq!(
+    ///     system.pre_update_deviation(&rating, now),
+    ///     system.preview_deviation(&rating, now - Periods(1.0))
+    /// );
+    /// assert!(system.pre_update_deviation(&rating, now) < system.preview_deviation(&rating, now));
+    /// ```
+    pub fn pre_update_deviation(&self, us: &Rating, now: Instant) -> RatingDifference {
+        self.preview_deviation(us, now - Periods(1.0)) // Notable change!
     }
 
-    /// Update the ratings of both players, given the score of a game between
-    /// between them.
+    /// Sample the previewed deviation of a rating without any further games,
+    /// at `steps + 1` instants spaced by `step`, starting at the instant of
+    /// the rating. The deviation is limited to the maximum deviation.
     ///
-    /// # Errors
+    /// # Example
     ///
-    /// Errors if the internal iterative algorithm does not converge within
-    /// the maximum number of iterations. Will not happen when using default
-    /// parameters for the rating system.
-    pub fn update_ratings(
+    /// ```
+    /// use liglicko2::{Instant, Periods, RatingDifference, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut rating = system.new_rating();
+    /// rating.deviation = RatingDifference(50.0);
+    ///
+    /// let curve = system.decay_curve(&rating, Periods(7.0), 4);
+    /// assert_eq!(curve.len(), 5);
+    /// assert_eq!(curve[0], (Instant(0.0), RatingDifference(50.0)));
+    /// assert_eq!(curve[4].0, Instant(28.0));
+    /// assert!(curve.windows(2).all(|w| w[0].1 < w[1].1));
+    /// ```
+    pub fn decay_curve(
         &self,
-        first: &Rating,
-        second: &Rating,
-        score: Score,
-        now: Instant,
+        rating: &Rating,
+        step: Periods,
+        steps: u32,
+    ) -> Vec<(Instant, RatingDifference)> {
+        (0..=steps)
+            .map(|i| {
+                let at = rating.at + Periods(step.0 * f64::from(i));
+                (at, self.preview_deviation(rating, at))
+            })
+            .collect()
+    }
+
+    /// Combine multiple snapshots of the same player into a single estimate
+    /// at `now`.
+    ///
+    /// The deviation of each snapshot is first decayed to `now`, then the
+    /// rating values (and volatilities) are averaged with weights
+    /// `1 / deviation²`. The combined deviation is `1 / sqrt(Σ 1 / deviation²)`,
+    /// so it is smaller than the deviation of any single snapshot. If some
+    /// snapshots still have zero deviation at `now`, the result is the plain
+    /// mean of just those snapshots, with zero deviation.
+    ///
+    /// This assumes that the snapshots are *independent* estimates, e.g.,
+    /// from different shards that have seen disjoint sets of games. Combining
+    /// successive snapshots of the same rating history would count the same
+    /// games multiple times and overstate the confidence in the result.
+    ///
+    /// Returns a new rating at `now` if there are no snapshots.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    ///
+    /// let mut recent = system.new_rating();
+    /// recent.rating = RatingScalar(1700.0);
+    /// recent.deviation = RatingDifference(60.0);
+    /// recent.at = Instant(10.0);
+    ///
+    /// let mut stale = recent.clone();
+    /// stale.rating = RatingScalar(1500.0);
+    /// stale.at = Instant(0.0);
+    ///
+    /// let now = Instant(10.0);
+    /// let consolidated = system.consolidate(&[recent.clone(), stale], now);
+    /// assert_eq!(consolidated.at, now);
+    ///
+    /// // Closer to the more recent snapshot, and more certain than either.
+    /// assert!(consolidated.rating > RatingScalar(1600.0));
+    /// assert!(consolidated.rating < RatingScalar(1700.0));
+    /// assert!(consolidated.deviation < recent.deviation);
+    ///
+    /// // Snapshots with zero deviation outweigh all others.
+    /// let exact_system = RatingSystem::builder()
+    ///     .min_deviation(RatingDifference(0.0))
+    ///     .build();
+    /// let mut exact = recent.clone();
+    /// exact.rating = RatingScalar(1800.0);
+    /// exact.deviation = RatingDifference(0.0);
+    /// let consolidated = exact_system.consolidate(&[recent, exact], now);
+    /// assert_eq!(consolidated.rating, RatingScalar(1800.0));
+    /// assert_eq!(consolidated.deviation, RatingDifference(0.0));
+    /// ```
+    pub fn consolidate(&self, snapshots: &[Rating], now: Instant) -> Rating {
+        let mut total_weight = 0.0;
+        let mut total_rating = 0.0;
+        let mut total_volatility = 0.0;
+        let mut exact_count = 0;
+        let mut exact_rating = 0.0;
+        let mut exact_volatility = 0.0;
+
+        for snapshot in snapshots {
+            let snapshot = self.clamp_rating(snapshot);
+            let weight = 1.0 / self.preview_deviation(&snapshot, now).0.powi(2);
+            if weight.is_infinite() {
+                exact_count += 1;
+                exact_rating += snapshot.rating.0;
+                exact_volatility += snapshot.volatility.0;
+            } else {
+                total_weight += weight;
+                total_rating += weight * snapshot.rating.0;
+                total_volatility += weight * snapshot.volatility.0;
+            }
+        }
+
+        if snapshots.is_empty() {
+            return Rating {
+                at: now,
+                ..self.new_rating()
+            };
+        }
+
+        if exact_count > 0 {
+            // Snapshots without any uncertainty outweigh all others.
+            return self.clamp_rating(&Rating {
+                rating: RatingScalar(exact_rating / f64::from(exact_count)),
+                deviation: RatingDifference(0.0),
+                volatility: Volatility(exact_volatility / f64::from(exact_count)),
+                at: now,
+            });
+        }
+
+        self.clamp_rating(&Rating {
+            rating: RatingScalar(total_rating / total_weight),
+            deviation: RatingDifference(1.0 / total_weight.sqrt()),
+            volatility: Volatility(total_volatility / total_weight),
+            at: now,
+        })
+    }
+
+    /// Calculate the trend of a rating history, as the slope of a linear
+    /// regression of the rating values over their instants, in rating points
+    /// per rating period.
+    ///
+    /// Returns zero for fewer than two ratings, or if all ratings are at the
+    /// same instant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let history: Vec<_> = [(0.0, 1500.0), (1.0, 1510.0), (2.0, 1520.0)]
+    ///     .into_iter()
+    ///     .map(|(at, rating)| {
+    ///         let mut r = system.new_rating();
+    ///         r.rating = RatingScalar(rating);
+    ///         r.at = Instant(at);
+    ///         r
+    ///     })
+    ///     .collect();
+    ///
+    /// assert!((system.rating_velocity(&history).0 - 10.0).abs() < 1e-9);
+    /// ```
+    pub fn rating_velocity(&self, history: &[Rating]) -> RatingDifference {
+        if history.len() < 2 {
+            return RatingDifference(0.0);
+        }
+
+        let n = history.len() as f64;
+        let mean_at = history.iter().map(|rating| rating.at.0).sum::<f64>() / n;
+        let mean_rating = history
+            .iter()
+            .map(|rating| self.clamp_rating(rating).rating.0)
+            .sum::<f64>()
+            / n;
+
+        let mut covariance = 0.0;
+        let mut variance = 0.0;
+        for rating in history {
+            let at = rating.at.0 - mean_at;
+            covariance += at * (self.clamp_rating(rating).rating.0 - mean_rating);
+            variance += at * at;
+        }
+
+        if variance > 0.0 {
+            RatingDifference(covariance / variance)
+        } else {
+            RatingDifference(0.0)
+        }
+    }
+
+    /// Check if the rating is provisional at the given point in time, i.e.,
+    /// if its previewed deviation exceeds
+    /// [`RatingSystemBuilder::provisional_deviation()`].
+    pub fn is_provisional(&self, rating: &Rating, now: Instant) -> bool {
+        self.preview_deviation(rating, now) > self.provisional_deviation
+    }
+
+    /// Estimate how many games a player starting at `start` needs to play
+    /// until their rating is no longer provisional (see
+    /// [`RatingSystem::is_provisional()`]).
+    ///
+    /// Simulates drawn games against `typical_opponent`, whose rating stays
+    /// fixed. This is an estimate that ignores the decay of the deviation
+    /// between games. The result is capped at `100` games, which is also
+    /// returned if an update fails to converge.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingDifference, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let newcomer = system.new_rating();
+    /// let mut typical_opponent = system.new_rating();
+    /// typical_opponent.deviation = RatingDifference(60.0);
+    ///
+    /// let games = system.games_to_leave_provisional(&newcomer, &typical_opponent);
+    /// assert!(0 < games && games < 100);
+    ///
+    /// let mut established = newcomer.clone();
+    /// established.deviation = RatingDifference(60.0);
+    /// assert_eq!(system.games_to_leave_provisional(&established, &typical_opponent), 0);
+    /// ```
+    pub fn games_to_leave_provisional(&self, start: &Rating, typical_opponent: &Rating) -> u32 {
+        const MAX_GAMES: u32 = 100;
+
+        let mut rating = self.clamp_rating(start);
+
+        for games in 0..MAX_GAMES {
+            if !self.is_provisional(&rating, rating.at) {
+                return games;
+            }
+            match self.update_ratings_no_decay(&rating, typical_opponent, Score::DRAW) {
+                Ok((updated, _)) => rating = updated,
+                Err(_) => break,
+            }
+        }
+
+        MAX_GAMES
+    }
+
+    /// Calculate the rating value to display, blending the previously
+    /// displayed value with the current rating value according to
+    /// [`RatingSystemBuilder::display_smoothing()`]. This is an exponentially
+    /// weighted moving average, so that the displayed value does not jump on
+    /// every game.
+    ///
+    /// Only the displayed value is smoothed. The current rating remains
+    /// authoritative for everything else, like matchmaking.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::builder().display_smoothing(0.75).build();
+    /// let mut current = system.new_rating();
+    /// current.rating = RatingScalar(1600.0);
+    ///
+    /// assert_eq!(system.display_value(RatingScalar(1500.0), &current), RatingScalar(1525.0));
+    ///
+    /// let raw = RatingSystem::new();
+    /// assert_eq!(raw.display_value(RatingScalar(1500.0), &current), RatingScalar(1600.0));
+    /// ```
+    pub fn display_value(&self, previous_display: RatingScalar, current: &Rating) -> RatingScalar {
+        let current = self.clamp_rating(current).rating;
+        RatingScalar(
+            self.display_smoothing * previous_display.0
+                + (1.0 - self.display_smoothing) * current.0,
+        )
+    }
+
+    /// Format the rating for display like on Lichess: The rating value
+    /// rounded to an integer, followed by `?` if the rating is provisional.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut rating = system.new_rating();
+    /// rating.rating = RatingScalar(1532.4);
+    /// assert_eq!(system.display_rating(&rating, Instant(0.0)), "1532?");
+    ///
+    /// rating.deviation = RatingDifference(60.0);
+    /// assert_eq!(system.display_rating(&rating, Instant(0.0)), "1532");
+    /// ```
+    pub fn display_rating(&self, rating: &Rating, now: Instant) -> String {
+        format!(
+            "{}{}",
+            self.clamp_rating(rating).rating.0.round(),
+            if self.is_provisional(rating, now) {
+                "?"
+            } else {
+                ""
+            }
+        )
+    }
+
+    /// Calculate how many rating periods after the rating was last updated
+    /// its deviation will reach `stale_threshold`, if no games are played
+    /// until then.
+    ///
+    /// Returns `None` if the deviation is already at or above the threshold,
+    /// or if the threshold will never be reached, because it exceeds the
+    /// maximum deviation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingDifference, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let threshold = RatingDifference(110.0);
+    ///
+    /// let mut rating = system.new_rating();
+    /// rating.deviation = RatingDifference(60.0);
+    /// let periods = system.periods_until_stale(&rating, threshold).unwrap();
+    /// let deviation = system.preview_deviation(&rating, rating.at + periods);
+    /// assert!((deviation.0 - threshold.0).abs() < 1e-9);
+    ///
+    /// // Already stale.
+    /// rating.deviation = RatingDifference(200.0);
+    /// assert_eq!(system.periods_until_stale(&rating, threshold), None);
+    ///
+    /// // Never stale.
+    /// assert_eq!(system.periods_until_stale(&rating, RatingDifference(1000.0)), None);
+    /// ```
+    pub fn periods_until_stale(
+        &self,
+        rating: &Rating,
+        stale_threshold: RatingDifference,
+    ) -> Option<Periods> {
+        let rating = self.clamp_rating(rating);
+
+        if rating.deviation >= stale_threshold || stale_threshold > self.max_deviation {
+            return None;
+        }
+
+        let periods = Periods(
+            (stale_threshold.to_internal(self.internal_rating_scale).sq()
+                - rating
+                    .deviation
+                    .to_internal(self.internal_rating_scale)
+                    .sq())
+                / rating.volatility.sq(),
+        );
+
+        let periods = if self.discrete_periods {
+            Periods(periods.0.ceil())
+        } else {
+            periods
+        };
+
+        periods.0.is_finite().then_some(periods)
+    }
+
+    /// Calculate the deviation that a player will settle at, when playing one
+    /// game every `frequency` rating periods against opponents like
+    /// `typical_opponent`.
+    ///
+    /// This is the equilibrium where the reduction of the deviation by each
+    /// game (step 7 of the Glicko-2 algorithm) balances the growth between
+    /// games. It assumes the player is rated like the opponent, and has the
+    /// default volatility. The result is limited to the allowed range of
+    /// deviations, so frequent play plateaus at the minimum deviation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Periods, RatingDifference, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut opponent = system.new_rating();
+    /// opponent.deviation = RatingDifference(60.0);
+    ///
+    /// let weekly = system.steady_state_deviation(&opponent, Periods(7.0));
+    /// assert!(system.min_deviation() < weekly && weekly < system.max_deviation());
+    ///
+    /// // Playing more often settles at a lower deviation.
+    /// let daily = system.steady_state_deviation(&opponent, Periods(1.0));
+    /// assert!(daily < weekly);
+    ///
+    /// // Limited to the allowed range of deviations.
+    /// assert_eq!(
+    ///     system.steady_state_deviation(&opponent, Periods(0.0)),
+    ///     system.min_deviation()
+    /// );
+    /// let rare = system.steady_state_deviation(&opponent, Periods(1e9));
+    /// assert!(weekly < rare && rare <= system.max_deviation());
+    /// ```
+    pub fn steady_state_deviation(
+        &self,
+        typical_opponent: &Rating,
+        frequency: Periods,
+    ) -> RatingDifference {
+        let opponent = self.clamp_rating(typical_opponent);
+        let their_g = g(opponent.deviation.to_internal(self.internal_rating_scale));
+        let v = 1.0 / (their_g.powi(2) * 0.25);
+
+        let growth = frequency.clamp_non_negative().0
+            * self
+                .default_volatility
+                .clamp(self.min_volatility, self.max_volatility)
+                .sq();
+
+        // Solve 1 / phi^2 = 1 / (phi^2 + growth) + 1 / v for phi^2.
+        let phi_sq = (f64::sqrt(growth.powi(2) + 4.0 * growth * v) - growth) / 2.0;
+
+        InternalRatingDifference(f64::sqrt(phi_sq))
+            .to_external(self.internal_rating_scale)
+            .clamp(self.min_deviation, self.max_deviation)
+    }
+
+    /// Estimate by how much one more game against `typical_opponent` reduces
+    /// the expected [`deviance`](crate::deviance) of future predictions for
+    /// games between them.
+    ///
+    /// The expected deviance exceeds the irreducible uncertainty of the game
+    /// outcome by the information that is still missing about the true
+    /// rating of the player. This excess is computed by integrating over the
+    /// uncertainty of the rating, before and after the deviation shrinks per
+    /// step 7 of the Glicko-2 algorithm. The first player advantage and the
+    /// volatility are ignored.
+    ///
+    /// Diminishing returns show how many placement games are worth playing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut opponent = system.new_rating();
+    /// opponent.deviation = RatingDifference(60.0);
+    ///
+    /// let new_player = system.new_rating();
+    /// let mut placed = system.new_rating();
+    /// placed.deviation = RatingDifference(150.0);
+    ///
+    /// let early = system.marginal_deviance_gain(&new_player, &opponent, Instant(0.0));
+    /// let late = system.marginal_deviance_gain(&placed, &opponent, Instant(0.0));
+    /// assert!(early > late);
+    /// assert!(late > 0.0);
+    /// ```
+    pub fn marginal_deviance_gain(
+        &self,
+        rating: &Rating,
+        typical_opponent: &Rating,
+        now: Instant,
+    ) -> f64 {
+        let us = self.preview(rating, now);
+        let them = self.preview(typical_opponent, now);
+
+        let their_g = g(them.deviation);
+        let diff = (us.rating - them.rating).to_internal(self.internal_rating_scale);
+        let expected = expectation_value(
+            diff,
+            g(InternalRatingDifference::hypot(
+                us.deviation,
+                them.deviation,
+            )),
+        )
+        .value();
+        let v = 1.0 / (their_g.powi(2) * expected * (1.0 - expected));
+
+        let phi = us.deviation;
+        let phi_prime = InternalRatingDifference(1.0 / f64::sqrt(1.0 / phi.sq() + 1.0 / v));
+
+        excess_deviance(diff, their_g, phi) - excess_deviance(diff, their_g, phi_prime)
+    }
+
+    /// Calculate the expected score for the first player in a game against the
+    /// second player.
+    pub fn expected_score(&self, first: &Rating, second: &Rating, now: Instant) -> Score {
+        self.expected_score_with_advantage(first, second, now, self.first_advantage)
+    }
+
+    /// Like [`RatingSystem::expected_score()`], but additionally flags if
+    /// the prediction is based on a stale rating, i.e., if the previewed
+    /// deviation of either player exceeds
+    /// [`RatingSystemBuilder::provisional_deviation()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut a = system.new_rating();
+    /// a.deviation = RatingDifference(60.0);
+    /// let b = a.clone();
+    ///
+    /// let (_, stale) = system.expected_score_checked(&a, &b, Instant(0.0));
+    /// assert!(!stale);
+    ///
+    /// let (_, stale) = system.expected_score_checked(&a, &b, Instant(1000.0));
+    /// assert!(stale);
+    /// ```
+    pub fn expected_score_checked(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        now: Instant,
+    ) -> (Score, bool) {
+        let first = self.preview(first, now);
+        let second = self.preview(second, now);
+        let threshold = self
+            .provisional_deviation
+            .to_internal(self.internal_rating_scale);
+
+        (
+            self.expectation(&first, &second, self.first_advantage),
+            first.deviation.0 > threshold.0 || second.deviation.0 > threshold.0,
+        )
+    }
+
+    /// Estimate the variance of the win probability of the first player in a
+    /// game against the second player, induced by the uncertainty of both
+    /// ratings. This is not the variance `p * (1 - p)` of the game outcome.
+    ///
+    /// Uses the delta method: The variance of the rating difference (the sum
+    /// of both previewed deviations squared, on the internal scale) is
+    /// propagated through the slope of the logistic function at the
+    /// estimated rating difference. The approximation is good when the
+    /// deviations are small.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut a = system.new_rating();
+    /// let mut b = system.new_rating();
+    /// let uncertain = system.expected_score_variance(&a, &b, Instant(0.0));
+    ///
+    /// a.deviation = RatingDifference(50.0);
+    /// b.deviation = RatingDifference(50.0);
+    /// let certain = system.expected_score_variance(&a, &b, Instant(0.0));
+    /// assert!(certain < uncertain);
+    /// ```
+    pub fn expected_score_variance(&self, first: &Rating, second: &Rating, now: Instant) -> f64 {
+        let first = self.preview(first, now);
+        let second = self.preview(second, now);
+
+        let p = expectation_value(
+            (first.rating - second.rating + self.first_advantage)
+                .to_internal(self.internal_rating_scale),
+            1.0,
+        )
+        .value();
+
+        (p * (1.0 - p)).powi(2) * (first.deviation.sq() + second.deviation.sq())
+    }
+
+    /// Calculate the interval of `z` previewed deviations around the rating
+    /// value, e.g., `z = 1.96` for a 95% confidence interval.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingDifference, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut rating = system.new_rating();
+    /// rating.deviation = RatingDifference(50.0);
+    ///
+    /// let (low, high) = system.confidence_interval(&rating, 2.0, rating.at);
+    /// assert_eq!(low, RatingScalar(1400.0));
+    /// assert_eq!(high, RatingScalar(1600.0));
+    /// ```
+    pub fn confidence_interval(
+        &self,
+        rating: &Rating,
+        z: f64,
+        now: Instant,
+    ) -> (RatingScalar, RatingScalar) {
+        let rating = self.clamp_rating(rating);
+        let radius = z * self.preview_deviation(&rating, now);
+        (rating.rating - radius, rating.rating + radius)
+    }
+
+    /// Check if the confidence intervals (see
+    /// [`RatingSystem::confidence_interval()`]) of both players intersect,
+    /// i.e., if they are too close to call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingDifference, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut a = system.new_rating();
+    /// a.deviation = RatingDifference(50.0);
+    /// let mut b = a.clone();
+    /// b.rating = RatingScalar(1650.0);
+    ///
+    /// assert!(system.intervals_overlap(&a, &b, 2.0, a.at));
+    /// assert!(!system.intervals_overlap(&a, &b, 1.0, a.at));
+    /// ```
+    pub fn intervals_overlap(&self, first: &Rating, second: &Rating, z: f64, now: Instant) -> bool {
+        let (first_low, first_high) = self.confidence_interval(first, z, now);
+        let (second_low, second_high) = self.confidence_interval(second, z, now);
+        first_low <= second_high && second_low <= first_high
+    }
+
+    /// Calculate by how many combined standard deviations the rating of the
+    /// first player exceeds the rating of the second player, using the
+    /// previewed deviations. Negative if the second player is rated higher.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut a = system.new_rating();
+    /// a.rating = RatingScalar(1800.0);
+    /// a.deviation = RatingDifference(60.0);
+    /// let mut b = a.clone();
+    /// b.rating = RatingScalar(1500.0);
+    /// b.deviation = RatingDifference(80.0);
+    ///
+    /// let z = system.z_distance(&a, &b, a.at);
+    /// assert!((z - 3.0).abs() < 1e-9);
+    /// ```
+    pub fn z_distance(&self, first: &Rating, second: &Rating, now: Instant) -> f64 {
+        let first_deviation = self.preview_deviation(first, now);
+        let second_deviation = self.preview_deviation(second, now);
+
+        (self.clamp_rating(first).rating - self.clamp_rating(second).rating).0
+            / f64::hypot(first_deviation.0, second_deviation.0)
+    }
+
+    /// Calculate the expected score for the first player in a game against
+    /// the second player, ignoring the inherent advantage of the first
+    /// player.
+    pub fn expected_score_neutral(&self, first: &Rating, second: &Rating, now: Instant) -> Score {
+        self.expected_score_with_advantage(first, second, now, RatingDifference(0.0))
+    }
+
+    /// Calculate by how much the inherent advantage of the first player
+    /// raises their expected score in a game against the second player.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::builder()
+    ///     .first_advantage(RatingDifference(20.0))
+    ///     .build();
+    /// let a = system.new_rating();
+    /// let b = system.new_rating();
+    /// let now = Instant(0.0);
+    ///
+    /// let contribution = system.advantage_contribution(&a, &b, now);
+    /// assert!(contribution > Score(0.0));
+    /// let neutral = system.expected_score_neutral(&a, &b, now);
+    /// let expected = system.expected_score(&a, &b, now);
+    /// assert!(((neutral + contribution).value() - expected.value()).abs() < 1e-12);
+    /// ```
+    pub fn advantage_contribution(&self, first: &Rating, second: &Rating, now: Instant) -> Score {
+        self.expected_score(first, second, now) - self.expected_score_neutral(first, second, now)
+    }
+
+    /// Calculate the expected scores of both players in a game, where
+    /// `first` is the first player.
+    ///
+    /// The expected scores always add up to `1.0`. Note that
+    /// `expected_score(second, first, now)` is *not* the expected score of
+    /// the second player in the same game, because it would assign the
+    /// advantage of the first player to the second player.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingSystem};
+    ///
+    /// let system = RatingSystem::builder()
+    ///     .first_advantage(RatingDifference(20.0))
+    ///     .build();
+    /// let a = system.new_rating();
+    /// let b = system.new_rating();
+    ///
+    /// let (a_expected, b_expected) = system.expected_scores_both(&a, &b, Instant(0.0));
+    /// assert!(a_expected > b_expected);
+    /// assert_eq!(b_expected, a_expected.opposite());
+    /// assert_ne!(b_expected, system.expected_score(&b, &a, Instant(0.0)));
+    /// ```
+    pub fn expected_scores_both(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        now: Instant,
+    ) -> (Score, Score) {
+        let expected = self.expected_score(first, second, now);
+        (expected, expected.opposite())
+    }
+
+    /// Like [`RatingSystem::expected_score()`], but for players that may be
+    /// unrated. Returns `None` if either player is unrated, so that games
+    /// involving them can be excluded from predictions.
+    ///
+    /// See [`RatingSystem::expected_score_or_default()`] to instead treat
+    /// unrated players as new players.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let rated = system.new_rating();
+    ///
+    /// let now = Instant(0.0);
+    /// assert!(system.expected_score_opt(Some(&rated), Some(&rated), now).is_some());
+    /// assert!(system.expected_score_opt(Some(&rated), None, now).is_none());
+    /// ```
+    pub fn expected_score_opt(
+        &self,
+        first: Option<&Rating>,
+        second: Option<&Rating>,
+        now: Instant,
+    ) -> Option<Score> {
+        Some(self.expected_score(first?, second?, now))
+    }
+
+    /// Like [`RatingSystem::expected_score()`], but for players that may be
+    /// unrated. Unrated players are treated as new players with
+    /// [`RatingSystem::new_rating()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let new = system.new_rating();
+    ///
+    /// let now = Instant(0.0);
+    /// assert_eq!(
+    ///     system.expected_score_or_default(Some(&new), None, now),
+    ///     system.expected_score(&new, &new, now)
+    /// );
+    /// ```
+    pub fn expected_score_or_default(
+        &self,
+        first: Option<&Rating>,
+        second: Option<&Rating>,
+        now: Instant,
+    ) -> Score {
+        let new_rating = self.new_rating();
+        self.expected_score(
+            first.unwrap_or(&new_rating),
+            second.unwrap_or(&new_rating),
+            now,
+        )
+    }
+
+    /// Like [`RatingSystem::expected_score()`], but with the previewed
+    /// deviations of both players inflated by `extra`.
+    ///
+    /// Larger deviations pull the expected score towards `0.5`. This is
+    /// intended for conservative matchmaking, where underestimating the
+    /// uncertainty of a rating is worse than overestimating it, so that
+    /// lopsided pairings are avoided unless the rating gap is well
+    /// established. The inflated deviations are not limited by
+    /// [`RatingSystemBuilder::max_deviation()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut strong = system.new_rating();
+    /// strong.rating = RatingScalar(2000.0);
+    /// strong.deviation = RatingDifference(60.0);
+    /// let mut weak = strong.clone();
+    /// weak.rating = RatingScalar(1700.0);
+    ///
+    /// let now = strong.at;
+    /// let expected = system.expected_score(&strong, &weak, now);
+    /// let conservative = system.expected_score_conservative(&strong, &weak, now, RatingDifference(100.0));
+    /// assert!(Score(0.5) < conservative && conservative < expected);
+    /// ```
+    pub fn expected_score_conservative(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        now: Instant,
+        extra: RatingDifference,
+    ) -> Score {
+        let inflate = |rating: &Rating| {
+            let rating = self.clamp_rating(rating);
+            Previewed {
+                rating: rating.rating,
+                deviation: (self.preview_deviation(&rating, now) + extra)
+                    .to_internal(self.internal_rating_scale),
+            }
+        };
+
+        self.expectation(&inflate(first), &inflate(second), self.first_advantage)
+    }
+
+    /// Calculate the classic Elo expectation for the first player in a game
+    /// against the second player, ignoring deviations entirely (`g = 1`).
+    /// Accounts for the inherent advantage of the first player.
+    ///
+    /// Comparing with [`RatingSystem::expected_score()`] isolates the effect
+    /// of damping predictions by the deviations of both players.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingDifference, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::builder()
+    ///     .first_advantage(RatingDifference(0.0))
+    ///     .build();
+    /// let mut first = system.new_rating();
+    /// first.rating = RatingScalar(1900.0);
+    /// let second = system.new_rating();
+    ///
+    /// let elo = 1.0 / (1.0 + f64::powf(10.0, -400.0 / 400.0));
+    /// assert!((system.expected_score_elo(&first, &second).value() - elo).abs() < 1e-6);
+    ///
+    /// // Deviations pull predictions towards 0.5.
+    /// assert!(system.expected_score(&first, &second, first.at) < system.expected_score_elo(&first, &second));
+    /// ```
+    pub fn expected_score_elo(&self, first: &Rating, second: &Rating) -> Score {
+        expectation_value(
+            (self.clamp_rating(first).rating - self.clamp_rating(second).rating
+                + self.first_advantage)
+                .to_internal(self.internal_rating_scale),
+            1.0,
+        )
+    }
+
+    /// Calculate by how much the deviations of both players pull the expected
+    /// score towards `0.5`, compared to the classic Elo expectation of
+    /// [`RatingSystem::expected_score_elo()`].
+    ///
+    /// Explains why an uncertain matchup is predicted to be closer than the
+    /// rating gap alone suggests.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingDifference, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut first = system.new_rating();
+    /// first.rating = RatingScalar(1900.0);
+    /// let second = system.new_rating();
+    /// let now = first.at;
+    ///
+    /// let uncertain = system.indifference_shrinkage(&first, &second, now);
+    ///
+    /// first.deviation = RatingDifference(45.0);
+    /// let established = system.indifference_shrinkage(&first, &second, now);
+    ///
+    /// assert!(0.0 < established && established < uncertain);
+    /// ```
+    pub fn indifference_shrinkage(&self, first: &Rating, second: &Rating, now: Instant) -> f64 {
+        (self.expected_score_elo(first, second).value() - 0.5).abs()
+            - (self.expected_score(first, second, now).value() - 0.5).abs()
+    }
+
+    /// Calculate the mean expected score of `me` as the first player against
+    /// a random opponent from `pool`.
+    ///
+    /// Returns [`Score::DRAW`] for an empty pool. Same as
+    /// [`RatingSystem::expected_schedule_points()`], with the pool as the
+    /// schedule.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let me = system.new_rating();
+    ///
+    /// let pool: Vec<_> = [1300.0, 1500.0, 1700.0]
+    ///     .into_iter()
+    ///     .map(|rating| {
+    ///         let mut opponent = system.new_rating();
+    ///         opponent.rating = RatingScalar(rating);
+    ///         opponent
+    ///     })
+    ///     .collect();
+    ///
+    /// let now = Instant(0.0);
+    /// let mean = system.expected_score_vs_pool(&me, &pool, now);
+    /// let expected = pool
+    ///     .iter()
+    ///     .map(|opponent| system.expected_score(&me, opponent, now).value())
+    ///     .sum::<f64>()
+    ///     / 3.0;
+    /// assert!((mean.value() - expected).abs() < 1e-12);
+    /// ```
+    pub fn expected_score_vs_pool(&self, me: &Rating, pool: &[Rating], now: Instant) -> Score {
+        self.expected_schedule_points(me, pool, now)
+    }
+
+    /// Rate how fair a game between the first and second player would be,
+    /// from `1.0` for an even game to `0.0` for a certain result. This is
+    /// `1 - 2 * |expected_score - 0.5|`, consistent with
+    /// [`RatingSystem::expected_score()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let me = system.new_rating();
+    /// let mut peer = system.new_rating();
+    /// peer.rating = RatingScalar(1480.0);
+    /// let mut master = system.new_rating();
+    /// master.rating = RatingScalar(2400.0);
+    ///
+    /// let now = Instant(0.0);
+    /// assert!(system.match_fairness(&me, &peer, now) > 0.9);
+    /// assert!(system.match_fairness(&me, &master, now) < 0.5);
+    /// ```
+    pub fn match_fairness(&self, first: &Rating, second: &Rating, now: Instant) -> f64 {
+        1.0 - 2.0 * (self.expected_score(first, second, now).value() - 0.5).abs()
+    }
+
+    /// Like [`RatingSystem::expected_score()`], but uses the rating values
+    /// as they are, even if they are outside of the bounds of the rating
+    /// system. Deviations and volatilities are still clamped.
+    ///
+    /// Rating updates always clamp rating values, so this is only different
+    /// for ratings that were stored with other bounds, e.g., migrated from a
+    /// previous configuration or computed with
+    /// [`RatingSystemBuilder::unbounded_rating()`]. For such ratings, the
+    /// order among players above the maximum rating (or below the minimum
+    /// rating) is preserved, where clamping would erase it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::builder()
+    ///     .max_rating(RatingScalar(3000.0))
+    ///     .build();
+    ///
+    /// let mut first = system.new_rating();
+    /// first.rating = RatingScalar(3300.0);
+    /// let mut second = system.new_rating();
+    /// second.rating = RatingScalar(3100.0);
+    ///
+    /// let now = Instant(0.0);
+    /// assert!(system.expected_score_unclamped(&first, &second, now) > system.expected_score(&first, &second, now));
+    /// ```
+    pub fn expected_score_unclamped(&self, first: &Rating, second: &Rating, now: Instant) -> Score {
+        let unclamped = |rating: &Rating| Previewed {
+            rating: rating.rating,
+            ..self.preview(rating, now)
+        };
+
+        self.expectation(&unclamped(first), &unclamped(second), self.first_advantage)
+    }
+
+    /// Decompose the expected score of the first player against the second
+    /// player into a win probability and the given draw rate, and return
+    /// the win probability.
+    ///
+    /// The expected score counts draws as half a point, so
+    /// `expected = win + draw / 2`. The loss probability is
+    /// `1 - win - draw`. The draw rate is limited, so that none of the
+    /// components are negative: Lopsided games cannot be drawn more often
+    /// than twice the expected score of the underdog.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut first = system.new_rating();
+    /// first.rating = RatingScalar(1700.0);
+    /// let second = system.new_rating();
+    ///
+    /// let now = Instant(0.0);
+    /// let draw_rate = Score(0.2);
+    /// let win = system.draw_adjusted_expected_score(&first, &second, now, draw_rate);
+    ///
+    /// let expected = system.expected_score(&first, &second, now);
+    /// assert!((win.value() + draw_rate.value() / 2.0 - expected.value()).abs() < 1e-12);
+    /// ```
+    pub fn draw_adjusted_expected_score(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        now: Instant,
+        observed_draw_rate: Score,
+    ) -> Score {
+        let expected = self.expected_score(first, second, now).value();
+        let draw_rate = observed_draw_rate
+            .value()
+            .min(2.0 * f64::min(expected, 1.0 - expected))
+            .max(0.0);
+        Score(expected - draw_rate / 2.0)
+    }
+
+    /// Attribute the [`deviance`](crate::deviance) of the prediction for a
+    /// game between the first and second player to its inputs, by
+    /// calculating the partial derivatives with respect to the rating gap,
+    /// the combined previewed deviation and the inherent advantage of the
+    /// first player.
+    ///
+    /// Like [`deviance_gradient_wrt_rating()`](crate::deviance_gradient_wrt_rating),
+    /// all derivatives are zero when the expected score is outside of the
+    /// range where the deviance clamps its input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut favorite = system.new_rating();
+    /// favorite.rating = RatingScalar(1800.0);
+    /// let underdog = system.new_rating();
+    ///
+    /// // The favorite lost: A smaller rating gap and a larger deviation
+    /// // would have reduced the deviance.
+    /// let attribution = system.error_attribution(&favorite, &underdog, Score::LOSS, Instant(0.0));
+    /// assert!(attribution.rating_gap > 0.0);
+    /// assert!(attribution.deviation < 0.0);
+    /// assert_eq!(attribution.first_advantage, attribution.rating_gap);
+    /// ```
+    pub fn error_attribution(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        actual: Score,
+        now: Instant,
+    ) -> Attribution {
+        let first = self.preview(first, now);
+        let second = self.preview(second, now);
+
+        let diff = (first.rating - second.rating + self.first_advantage)
+            .to_internal(self.internal_rating_scale);
+        let deviation = InternalRatingDifference::hypot(first.deviation, second.deviation);
+        let g = g(deviation);
+
+        // The logit of the expected score is g * diff.
+        let d_logit = deviance_gradient_wrt_logit(expectation_value(diff, g), actual);
+        let d_gap = d_logit * g / self.internal_rating_scale;
+        let d_g = -3.0 * deviation.0 / PI.powi(2) * g.powi(3);
+
+        Attribution {
+            rating_gap: d_gap,
+            deviation: d_logit * diff.0 * d_g / self.internal_rating_scale,
+            first_advantage: d_gap,
+        }
+    }
+
+    /// Calculate the expected score of the first player against the second
+    /// player exactly as it is used in the rating update at `now`.
+    ///
+    /// This differs from [`RatingSystem::expected_score()`] in two ways:
+    ///
+    /// - Like Glicko-2, the update only considers the deviation of the
+    ///   opponent, not the combined deviation of both players.
+    /// - The deviation of the opponent is previewed one rating period before
+    ///   `now` (see [`RatingSystem::pre_update_deviation()`]).
+    ///
+    /// Use this to track the deviance of the same predictions that drive the
+    /// rating updates. [`RatingSystem::expected_score()`] remains the better
+    /// prediction of the outcome.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut first = system.new_rating();
+    /// first.rating = RatingScalar(1700.0);
+    /// let second = system.new_rating();
+    ///
+    /// // Ignoring the deviation of the first player makes the prediction more
+    /// // decisive.
+    /// let now = Instant(0.0);
+    /// assert!(system.expected_score_as_update_sees_it(&first, &second, now) > system.expected_score(&first, &second, now));
+    /// ```
+    pub fn expected_score_as_update_sees_it(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        now: Instant,
+    ) -> Score {
+        let first = self.clamp_rating(first);
+        let second = self.clamp_rating(second);
+
+        expectation_value(
+            (first.rating - second.rating + self.first_advantage)
+                .to_internal(self.internal_rating_scale),
+            g(self
+                .pre_update_deviation(&second, now)
+                .to_internal(self.internal_rating_scale)),
+        )
+    }
+
+    /// Preview a rating at `now` once, to efficiently calculate expected
+    /// scores against many opponents with
+    /// [`PreparedRating::expected_score_against()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let me = system.new_rating();
+    /// let mut opponent = system.new_rating();
+    /// opponent.rating = RatingScalar(1650.0);
+    ///
+    /// let now = Instant(3.0);
+    /// let prepared_me = system.prepared(&me, now);
+    /// let prepared_opponent = system.prepared(&opponent, now);
+    ///
+    /// let expected = prepared_me.expected_score_against(&prepared_opponent);
+    /// assert!((expected.value() - system.expected_score(&me, &opponent, now).value()).abs() < 1e-12);
+    /// ```
+    pub fn prepared(&self, rating: &Rating, now: Instant) -> PreparedRating {
+        let previewed = self.preview(rating, now);
+
+        PreparedRating {
+            rating: (previewed.rating - self.default_rating)
+                .to_internal(self.internal_rating_scale)
+                .0,
+            deviation_sq: previewed.deviation.sq(),
+            first_advantage: self
+                .first_advantage
+                .to_internal(self.internal_rating_scale)
+                .0,
+        }
+    }
+
+    /// Calculate the probability, before the game, that `winner` would win
+    /// against `loser`. Low values indicate big upsets, which can be used to
+    /// surface surprising results.
+    ///
+    /// This is the same as [`RatingSystem::expected_score()`], so `winner`
+    /// takes the role of the first player and receives the inherent
+    /// advantage.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut favorite = system.new_rating();
+    /// favorite.rating = RatingScalar(2200.0);
+    /// let underdog = system.new_rating();
+    ///
+    /// let now = Instant(0.0);
+    /// assert!(system.upset_probability(&underdog, &favorite, now) < Score(0.2));
+    /// assert!(system.upset_probability(&favorite, &underdog, now) > Score(0.8));
+    /// ```
+    pub fn upset_probability(&self, winner: &Rating, loser: &Rating, now: Instant) -> Score {
+        self.expected_score(winner, loser, now)
+    }
+
+    /// Find the maximum likelihood rating of a player, given the results of
+    /// games against opponents with known ratings. The player is the first
+    /// player in each game.
+    ///
+    /// Unlike sequential rating updates, the result does not depend on the
+    /// order of the games. It is a performance rating that properly accounts
+    /// for the deviations of the opponents.
+    ///
+    /// Returns `None` if there are no games, or if Newton's method does not
+    /// converge, which is in particular the case when all games were won
+    /// (or all lost).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut weaker = system.new_rating();
+    /// weaker.rating = RatingScalar(1400.0);
+    /// let mut stronger = system.new_rating();
+    /// stronger.rating = RatingScalar(1600.0);
+    ///
+    /// let games = [(weaker.clone(), Score::WIN), (stronger.clone(), Score::LOSS)];
+    /// let rating = system.mle_rating(&games, Instant(0.0)).unwrap();
+    /// assert!((rating.0 - 1500.0).abs() < 1e-6);
+    ///
+    /// assert_eq!(system.mle_rating(&[(weaker, Score::WIN)], Instant(0.0)), None);
+    /// ```
+    pub fn mle_rating(&self, games: &[(Rating, Score)], now: Instant) -> Option<RatingScalar> {
+        if games.is_empty() {
+            return None;
+        }
+
+        let opponents: Vec<(Previewed, f64)> = games
+            .iter()
+            .map(|(opponent, score)| {
+                (
+                    self.preview(opponent, now),
+                    score.clamp(Score::LOSS, Score::WIN).value(),
+                )
+            })
+            .collect();
+
+        let mut rating = self.default_rating;
+        for _ in 0..self.max_convergence_iterations {
+            let mut gradient = 0.0;
+            let mut curvature = 0.0;
+            for (opponent, actual) in &opponents {
+                let their_g = g(opponent.deviation);
+                let expected = expectation_value(
+                    (rating - opponent.rating + self.first_advantage)
+                        .to_internal(self.internal_rating_scale),
+                    their_g,
+                )
+                .value();
+                gradient += (actual - expected) * their_g;
+                curvature += expected * (1.0 - expected) * their_g.powi(2);
+            }
+
+            let step = InternalRatingDifference(gradient / curvature);
+            if !step.0.is_finite() {
+                return None;
+            }
+
+            rating += step.to_external(self.internal_rating_scale);
+            if step.0.abs() < self.convergence_tolerance {
+                return rating.0.is_finite().then_some(rating);
+            }
+        }
+
+        None
+    }
+
+    /// Calculate the average expected score of `me` as the first player in
+    /// games against each of the `opponents`. Draws are folded into the
+    /// expectation as usual. Multiply by the number of games to get the
+    /// expected total points.
+    ///
+    /// Returns [`Score::DRAW`] if there are no opponents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let me = system.new_rating();
+    /// let mut stronger = system.new_rating();
+    /// stronger.rating = RatingScalar(1700.0);
+    /// let mut weaker = system.new_rating();
+    /// weaker.rating = RatingScalar(1300.0);
+    ///
+    /// let points = system.expected_schedule_points(&me, &[stronger, weaker], Instant(0.0));
+    /// assert!((points.value() - 0.5).abs() < 1e-12);
+    /// ```
+    pub fn expected_schedule_points(
+        &self,
+        me: &Rating,
+        opponents: &[Rating],
+        now: Instant,
+    ) -> Score {
+        if opponents.is_empty() {
+            return Score::DRAW;
+        }
+
+        let me = self.preview(me, now);
+        let total: f64 = opponents
+            .iter()
+            .map(|opponent| {
+                self.expectation(&me, &self.preview(opponent, now), self.first_advantage)
+                    .value()
+            })
+            .sum();
+
+        Score(total / opponents.len() as f64)
+    }
+
+    /// Calculate the probabilities that the first player wins exactly
+    /// `0..=games` games of a match against the second player.
+    ///
+    /// Each game is treated as an independent win or loss, with the expected
+    /// score as the probability of a win. Draws are not modeled separately.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut alice = system.new_rating();
+    /// alice.rating = RatingScalar(1700.0);
+    /// let bob = system.new_rating();
+    ///
+    /// let distribution = system.match_scoreline_distribution(&alice, &bob, 5, Instant(0.0));
+    /// assert_eq!(distribution.len(), 6);
+    /// assert!((distribution.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn match_scoreline_distribution(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        games: u32,
+        now: Instant,
+    ) -> Vec<f64> {
+        let p = self.expected_score(first, second, now).value();
+
+        let mut distribution = vec![1.0];
+        for _ in 0..games {
+            let mut next = vec![0.0; distribution.len() + 1];
+            for (wins, probability) in distribution.into_iter().enumerate() {
+                next[wins] += probability * (1.0 - p);
+                next[wins + 1] += probability * p;
+            }
+            distribution = next;
+        }
+        distribution
+    }
+
+    /// Calculate the expected score for the first player in a future game
+    /// against the second player, assuming that neither plays any games
+    /// until then.
+    ///
+    /// Unlike [`RatingSystem::expected_score()`], which silently uses the
+    /// ratings as-is for instants before their last update, this rejects
+    /// such queries.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `future` is before the last update of either rating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut first = system.new_rating();
+    /// first.at = Instant(10.0);
+    /// let second = system.new_rating();
+    ///
+    /// let future = Instant(20.0);
+    /// assert_eq!(
+    ///     system.expected_score_at(&first, &second, future).unwrap(),
+    ///     system.expected_score(&first, &second, future)
+    /// );
+    ///
+    /// assert!(system.expected_score_at(&first, &second, Instant(5.0)).is_err());
+    /// ```
+    pub fn expected_score_at(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        future: Instant,
+    ) -> Result<Score, PastInstantError> {
+        if future < first.at || future < second.at {
+            return Err(PastInstantError { _priv: () });
+        }
+
+        Ok(self.expected_score(first, second, future))
+    }
+
+    /// Calculate the expected score for the first player in a game against the
+    /// second player, where the inherent advantage goes to whichever player
+    /// has the higher rating, rather than to the first player.
+    ///
+    /// If both players have the same rating, the first player gets the
+    /// advantage.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::builder()
+    ///     .first_advantage(RatingDifference(10.0))
+    ///     .build();
+    /// let now = Instant(0.0);
+    ///
+    /// // Tie: the first player gets the advantage.
+    /// let a = system.new_rating();
+    /// let b = system.new_rating();
+    /// assert_eq!(
+    ///     system.expected_score_advantage_to_stronger(&a, &b, now),
+    ///     system.expected_score(&a, &b, now)
+    /// );
+    /// assert!(system.expected_score_advantage_to_stronger(&a, &b, now).value() > 0.5);
+    ///
+    /// // Otherwise, the stronger second player gets the advantage.
+    /// let mut stronger = system.new_rating();
+    /// stronger.rating = RatingScalar(1600.0);
+    /// let expected = system.expected_score(&stronger, &a, now).opposite();
+    /// let actual = system.expected_score_advantage_to_stronger(&a, &stronger, now);
+    /// assert!((actual.value() - expected.value()).abs() < 1e-12);
+    /// ```
+    pub fn expected_score_advantage_to_stronger(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        now: Instant,
+    ) -> Score {
+        let first = self.preview(first, now);
+        let second = self.preview(second, now);
+
+        let advantage = if first.rating >= second.rating {
+            self.first_advantage
+        } else {
+            -self.first_advantage
+        };
+
+        self.expectation(&first, &second, advantage)
+    }
+
+    /// Calculate the expected fraction of points for the first player in a
+    /// match against the second player, where the first player is the first
+    /// to move in `first_moves` games, and the second player in
+    /// `second_moves` games.
+    ///
+    /// Only the ratio of the counts matters, so they act as weights for
+    /// the two color assignments. If both are zero, the expectation does not
+    /// consider any inherent advantage.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::builder()
+    ///     .first_advantage(RatingDifference(10.0))
+    ///     .build();
+    /// let a = system.new_rating();
+    /// let b = system.new_rating();
+    /// let now = Instant(0.0);
+    ///
+    /// // Alternating colors cancels the inherent advantage.
+    /// let balanced = system.expected_match_score_colors(&a, &b, 2, 2, now);
+    /// assert!((balanced.value() - 0.5).abs() < 1e-12);
+    ///
+    /// // Always moving first is like a single game as the first player.
+    /// assert_eq!(
+    ///     system.expected_match_score_colors(&a, &b, 3, 0, now),
+    ///     system.expected_score(&a, &b, now)
+    /// );
+    ///
+    /// assert_eq!(system.expected_match_score_colors(&a, &b, 0, 0, now), Score::DRAW);
+    /// ```
+    pub fn expected_match_score_colors(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        first_moves: u32,
+        second_moves: u32,
+        now: Instant,
+    ) -> Score {
+        if first_moves == 0 && second_moves == 0 {
+            return self.expected_score_with_advantage(first, second, now, RatingDifference(0.0));
+        }
+
+        let first_moves = f64::from(first_moves);
+        let second_moves = f64::from(second_moves);
+
+        Score(
+            (first_moves
+                * self
+                    .expected_score_with_advantage(first, second, now, self.first_advantage)
+                    .value()
+                + second_moves
+                    * self
+                        .expected_score_with_advantage(first, second, now, -self.first_advantage)
+                        .value())
+                / (first_moves + second_moves),
+        )
+    }
+
+    /// Calculate the expected scores for all pairings among the given
+    /// players. Entry `[i][j]` is the expected score of `players[i]` as the
+    /// first player against `players[j]`.
+    ///
+    /// The diagonal contains the expectation of each player against
+    /// themselves, which is indifferent except for the inherent advantage of
+    /// the first player.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let players: Vec<_> = [1300.0, 1500.0, 1700.0]
+    ///     .into_iter()
+    ///     .map(|rating| {
+    ///         let mut player = system.new_rating();
+    ///         player.rating = RatingScalar(rating);
+    ///         player
+    ///     })
+    ///     .collect();
+    ///
+    /// let matrix = system.expectation_matrix(&players, Instant(0.0));
+    /// assert_eq!(matrix.len(), 3);
+    ///
+    /// // Without first player advantage, expectations of both sides of a
+    /// // pairing add up to 1.
+    /// for i in 0..3 {
+    ///     for j in 0..3 {
+    ///         assert!((matrix[i][j].value() + matrix[j][i].value() - 1.0).abs() < 1e-12);
+    ///     }
+    /// }
+    /// assert!(matrix[2][0].value() > 0.5);
+    /// ```
+    pub fn expectation_matrix(&self, players: &[Rating], now: Instant) -> Vec<Vec<Score>> {
+        let previewed: Vec<Previewed> = players
+            .iter()
+            .map(|player| self.preview(player, now))
+            .collect();
+
+        previewed
+            .iter()
+            .map(|first| {
+                previewed
+                    .iter()
+                    .map(|second| self.expectation(first, second, self.first_advantage))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Select the candidates from the pool, against whom the expected score
+    /// of `me` (as the first player) lies within the inclusive
+    /// `score_window`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let me = system.new_rating();
+    ///
+    /// let pool: Vec<_> = [1000.0, 1450.0, 1550.0, 2000.0]
+    ///     .into_iter()
+    ///     .map(|rating| {
+    ///         let mut candidate = system.new_rating();
+    ///         candidate.rating = RatingScalar(rating);
+    ///         candidate
+    ///     })
+    ///     .collect();
+    ///
+    /// // Keeps only the close matches, and drops the far weaker and far
+    /// // stronger candidates.
+    /// let opponents = system.filter_opponents(&me, &pool, Instant(0.0), (Score(0.4), Score(0.6)));
+    /// let ratings: Vec<_> = opponents.iter().map(|opponent| opponent.rating).collect();
+    /// assert_eq!(ratings, [RatingScalar(1450.0), RatingScalar(1550.0)]);
+    /// ```
+    pub fn filter_opponents<'a>(
+        &self,
+        me: &Rating,
+        pool: &'a [Rating],
+        now: Instant,
+        (min_score, max_score): (Score, Score),
+    ) -> Vec<&'a Rating> {
+        let me = self.preview(me, now);
+
+        pool.iter()
+            .filter(|candidate| {
+                let expected =
+                    self.expectation(&me, &self.preview(candidate, now), self.first_advantage);
+                min_score <= expected && expected <= max_score
+            })
+            .collect()
+    }
+
+    fn expected_score_with_advantage(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        now: Instant,
+        advantage: RatingDifference,
+    ) -> Score {
+        self.expectation(
+            &self.preview(first, now),
+            &self.preview(second, now),
+            advantage,
+        )
+    }
+
+    fn preview(&self, rating: &Rating, now: Instant) -> Previewed {
+        let rating = self.clamp_rating(rating);
+
+        Previewed {
+            rating: rating.rating,
+            deviation: self
+                .preview_deviation(&rating, now)
+                .to_internal(self.internal_rating_scale),
+        }
+    }
+
+    /// Factor `g` of the combined previewed deviations of both players, by
+    /// which the rating difference is scaled in the expectation.
+    pub(crate) fn combined_g(&self, first: &Rating, second: &Rating, now: Instant) -> f64 {
+        g(InternalRatingDifference::hypot(
+            self.preview(first, now).deviation,
+            self.preview(second, now).deviation,
+        ))
+    }
+
+    fn expectation(
+        &self,
+        first: &Previewed,
+        second: &Previewed,
+        advantage: RatingDifference,
+    ) -> Score {
+        expectation_value(
+            (first.rating - second.rating + advantage).to_internal(self.internal_rating_scale),
+            g(InternalRatingDifference::hypot(
+                first.deviation,
+                second.deviation,
+            )),
+        )
+    }
+
+    /// Calculate the rating difference between the first and second player
+    /// that leads to the given odds of winning for the first player, when
+    /// the players have the given deviations. Accounts for the inherent
+    /// advantage of the first player, so `odds = 1.0` returns the negated
+    /// advantage.
+    ///
+    /// The result saturates at the largest rating difference allowed by the
+    /// system, so that extreme odds (including `0.0` and infinity) do not
+    /// produce unbounded results.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingDifference, RatingSystem};
+    ///
+    /// let system = RatingSystem::builder()
+    ///     .first_advantage(RatingDifference(10.0))
+    ///     .build();
+    /// let deviation = system.min_deviation();
+    ///
+    /// // Even odds exactly compensate the inherent advantage.
+    /// assert_eq!(
+    ///     system.rating_gap_for_odds(1.0, deviation, deviation),
+    ///     RatingDifference(-10.0)
+    /// );
+    ///
+    /// // Extreme odds saturate at the largest possible rating difference.
+    /// let max_gap = system.max_rating() - system.min_rating();
+    /// assert_eq!(system.rating_gap_for_odds(0.0, deviation, deviation), -max_gap);
+    /// assert_eq!(
+    ///     system.rating_gap_for_odds(f64::INFINITY, deviation, deviation),
+    ///     max_gap
+    /// );
+    /// ```
+    pub fn rating_gap_for_odds(
+        &self,
+        odds: f64,
+        first_deviation: RatingDifference,
+        second_deviation: RatingDifference,
+    ) -> RatingDifference {
+        let max_gap = self.max_rating_gap();
+
+        (self.rating_gap_for_logit(f64::ln(odds), first_deviation, second_deviation)
+            - self.first_advantage)
+            .clamp(-max_gap, max_gap)
+    }
+
+    /// Calculate the rating of an established opponent (with the minimum
+    /// deviation), such that the expected score of `me` as the first player
+    /// is `target`. Accounts for the inherent advantage of the first player.
+    ///
+    /// Saturates at the minimum and maximum rating of the system, if the
+    /// target is not reachable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, Rating, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let me = system.new_rating();
+    ///
+    /// let opponent = Rating {
+    ///     rating: system.opponent_rating_for_target(&me, Score(0.4), Instant(0.0)),
+    ///     deviation: system.min_deviation(),
+    ///     ..me.clone()
+    /// };
+    /// let expected = system.expected_score(&me, &opponent, Instant(0.0));
+    /// assert!((expected.value() - 0.4).abs() < 1e-9);
+    ///
+    /// assert_eq!(system.opponent_rating_for_target(&me, Score::WIN, Instant(0.0)), system.min_rating());
+    /// ```
+    pub fn opponent_rating_for_target(
+        &self,
+        me: &Rating,
+        target: Score,
+        now: Instant,
+    ) -> RatingScalar {
+        let me = self.clamp_rating(me);
+
+        self.clamp_rating_value(
+            me.rating
+                - self.rating_gap_for_odds(
+                    target.value() / target.opposite().value(),
+                    self.preview_deviation(&me, now),
+                    self.min_deviation,
+                ),
+        )
+    }
+
+    /// Calculate the rating of a bot that wins with the given probability
+    /// against a player rated `vs_rating`, when both ratings have the minimum
+    /// deviation. The inherent advantage of the first player is ignored.
+    ///
+    /// Saturates at the minimum and maximum rating of the system.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let bot = system.bot_rating_from_winrate(Score(0.62), RatingScalar(1500.0));
+    /// assert!(bot > RatingScalar(1500.0));
+    ///
+    /// let mut bot_rating = system.new_rating();
+    /// bot_rating.rating = bot;
+    /// bot_rating.deviation = system.min_deviation();
+    /// let mut human = bot_rating.clone();
+    /// human.rating = RatingScalar(1500.0);
+    /// let expected = system.expected_score_neutral(&bot_rating, &human, bot_rating.at);
+    /// assert!((expected.value() - 0.62).abs() < 1e-9);
+    /// ```
+    pub fn bot_rating_from_winrate(&self, winrate: Score, vs_rating: RatingScalar) -> RatingScalar {
+        let max_gap = self.max_rating_gap();
+
+        self.clamp_rating_value(
+            self.clamp_rating_value(vs_rating)
+                + self
+                    .rating_gap_for_logit(
+                        f64::ln(winrate.value() / winrate.opposite().value()),
+                        self.min_deviation,
+                        self.min_deviation,
+                    )
+                    .clamp(-max_gap, max_gap),
+        )
+    }
+
+    fn rating_gap_for_logit(
+        &self,
+        logit: f64,
+        first_deviation: RatingDifference,
+        second_deviation: RatingDifference,
+    ) -> RatingDifference {
+        let deviation = InternalRatingDifference::hypot(
+            first_deviation
+                .clamp(self.min_deviation, self.max_deviation)
+                .to_internal(self.internal_rating_scale),
+            second_deviation
+                .clamp(self.min_deviation, self.max_deviation)
+                .to_internal(self.internal_rating_scale),
+        );
+
+        InternalRatingDifference(logit / g(deviation)).to_external(self.internal_rating_scale)
+    }
+
+    /// Update the ratings of both players, given the score of a game between
+    /// between them.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the internal iterative algorithm does not converge within
+    /// the maximum number of iterations. Will not happen when using default
+    /// parameters for the rating system.
+    pub fn update_ratings(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        score: Score,
+        now: Instant,
+    ) -> Result<(Rating, Rating), ConvergenceError> {
+        self.update_ratings_impl(
+            first,
+            second,
+            (score, score.opposite()),
+            Some(now),
+            (self.first_advantage, -self.first_advantage),
+        )
+    }
+
+    /// Update the ratings of both players, given the score of a game between
+    /// them, without any decay of the deviations for the time since their
+    /// last update. Ratings keep their instants of the last update.
+    ///
+    /// This is appropriate when many games share a logical timestamp, like
+    /// the rounds of a tournament played on the same day, where the time
+    /// between games would otherwise be counted multiple times. The
+    /// deviations still shrink due to the games themselves.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, Rating, RatingDifference, RatingScalar, RatingSystem, Score, Volatility};
+    ///
+    /// let system = RatingSystem::new();
+    ///
+    /// let first = Rating {
+    ///     rating: RatingScalar(1500.0),
+    ///     deviation: RatingDifference(200.0),
+    ///     volatility: Volatility(0.06),
+    ///     at: Instant(0.0),
+    /// };
+    /// let second = Rating {
+    ///     at: Instant(3.0),
+    ///     ..first.clone()
+    /// };
+    ///
+    /// let (first, second) = system.update_ratings_no_decay(&first, &second, Score::DRAW).unwrap();
+    /// assert!(first.deviation < RatingDifference(200.0));
+    /// assert_eq!(first.deviation, second.deviation);
+    /// assert_eq!(first.at, Instant(0.0));
+    /// assert_eq!(second.at, Instant(3.0));
+    /// ```
+    pub fn update_ratings_no_decay(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        score: Score,
+    ) -> Result<(Rating, Rating), ConvergenceError> {
+        self.update_ratings_impl(
+            first,
+            second,
+            (score, score.opposite()),
+            None,
+            (self.first_advantage, -self.first_advantage),
+        )
+    }
+
+    /// Update the rating of `us` with a single game against a synthetic
+    /// opponent, with the median rating and median deviation of all
+    /// opponents in `games`, and the average score of all games.
+    ///
+    /// This is a stabilization heuristic for short sessions, like a quick
+    /// streak of wins or losses, where sequential updates can overshoot. It
+    /// does not accumulate the information of each game like a proper batch
+    /// update, so the deviation shrinks only as much as for a single game.
+    ///
+    /// Returns the unchanged rating if there are no games.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let us = system.new_rating();
+    /// let games: Vec<_> = [1400.0, 1500.0, 2200.0]
+    ///     .into_iter()
+    ///     .map(|rating| {
+    ///         let mut opponent = system.new_rating();
+    ///         opponent.rating = RatingScalar(rating);
+    ///         (opponent, Score::WIN)
+    ///     })
+    ///     .collect();
+    ///
+    /// let updated = system.update_against_median(&us, &games, Instant(0.0)).unwrap();
+    /// assert!(updated.rating > us.rating);
+    /// ```
+    pub fn update_against_median(
+        &self,
+        us: &Rating,
+        games: &[(Rating, Score)],
+        now: Instant,
+    ) -> Result<Rating, ConvergenceError> {
+        if games.is_empty() {
+            return Ok(self.clamp_rating(us));
+        }
+
+        let opponent = Rating {
+            rating: RatingScalar(median(
+                games
+                    .iter()
+                    .map(|(opponent, _)| self.clamp_rating(opponent).rating.0)
+                    .collect(),
+            )),
+            deviation: RatingDifference(median(
+                games
+                    .iter()
+                    .map(|(opponent, _)| self.preview_deviation(opponent, now).0)
+                    .collect(),
+            )),
+            volatility: self.default_volatility,
+            at: now,
+        };
+
+        let score =
+            Score(games.iter().map(|(_, score)| score.value()).sum::<f64>() / games.len() as f64);
+
+        Ok(self.update_ratings(us, &opponent, score, now)?.0)
+    }
+
+    /// Like [`RatingSystem::update_ratings()`], but with independent
+    /// advantages for each player, instead of the configured advantage of the
+    /// first player.
+    ///
+    /// Each advantage is added to the rating difference from the perspective
+    /// of the respective player, when updating their rating.
+    /// [`RatingSystem::update_ratings()`] is equivalent to using
+    /// `first_advantage` and `-first_advantage`. Other combinations can
+    /// model richer games, like home-field advantage in addition to the
+    /// advantage of moving first.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::builder()
+    ///     .first_advantage(RatingDifference(20.0))
+    ///     .build();
+    /// let first = system.new_rating();
+    /// let second = system.new_rating();
+    /// let now = Instant(0.0);
+    ///
+    /// let (a, b) = system.update_ratings(&first, &second, Score::DRAW, now).unwrap();
+    /// let (c, d) = system
+    ///     .update_ratings_with_advantages(&first, &second, Score::DRAW, now, RatingDifference(20.0), RatingDifference(-20.0))
+    ///     .unwrap();
+    /// assert_eq!(a, c);
+    /// assert_eq!(b, d);
+    /// ```
+    pub fn update_ratings_with_advantages(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        score: Score,
+        now: Instant,
+        first_advantage: RatingDifference,
+        second_advantage: RatingDifference,
+    ) -> Result<(Rating, Rating), ConvergenceError> {
+        self.update_ratings_impl(
+            first,
+            second,
+            (score, score.opposite()),
+            Some(now),
+            (first_advantage, second_advantage),
+        )
+    }
+
+    /// Like [`RatingSystem::update_ratings()`], but additionally limits the
+    /// new rating values to the given per-player bounds.
+    ///
+    /// The per-player bounds should lie within the bounds of the rating
+    /// system, which take precedence.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lower bound is greater than the upper bound, or either
+    /// is NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let first = system.new_rating();
+    /// let second = system.new_rating();
+    ///
+    /// let (first, _) = system
+    ///     .update_ratings_bounded(
+    ///         &first,
+    ///         &second,
+    ///         Score::WIN,
+    ///         Instant(0.0),
+    ///         (system.min_rating(), RatingScalar(1550.0)),
+    ///         (system.min_rating(), system.max_rating()),
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(first.rating, RatingScalar(1550.0));
+    /// ```
+    pub fn update_ratings_bounded(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        score: Score,
+        now: Instant,
+        (first_min, first_max): (RatingScalar, RatingScalar),
+        (second_min, second_max): (RatingScalar, RatingScalar),
+    ) -> Result<(Rating, Rating), ConvergenceError> {
+        let (first, second) = self.update_ratings(first, second, score, now)?;
+
+        Ok((
+            self.clamp_rating(&Rating {
+                rating: first.rating.clamp(first_min, first_max),
+                ..first
+            }),
+            self.clamp_rating(&Rating {
+                rating: second.rating.clamp(second_min, second_max),
+                ..second
+            }),
+        ))
+    }
+
+    /// Like [`RatingSystem::update_ratings()`], but redistributes the rating
+    /// changes, so that the total rating of both players is conserved.
+    ///
+    /// With the rating changes `d1` and `d2` of the regular update, the
+    /// first player gains `(d1 - d2) / 2` and the second player gains
+    /// `(d2 - d1) / 2`. Deviations and volatilities are updated as usual.
+    ///
+    /// The regular rating changes differ in magnitude whenever the deviations
+    /// of the players differ, so this is only an approximation of the
+    /// Glicko-2 update: Players with uncertain ratings converge more slowly,
+    /// and established players move more than they otherwise would. The
+    /// total is not conserved if the new ratings have to be clamped to the
+    /// bounds of the rating system.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let first = system.new_rating();
+    /// let mut second = system.new_rating();
+    /// second.deviation = RatingDifference(80.0);
+    ///
+    /// let (first_prime, second_prime) = system
+    ///     .update_ratings_zero_sum(&first, &second, Score::WIN, Instant(0.0))
+    ///     .unwrap();
+    /// assert!(first_prime.rating > first.rating);
+    /// assert!(((first_prime.rating - first.rating) + (second_prime.rating - second.rating)).0.abs() < 1e-9);
+    /// ```
+    pub fn update_ratings_zero_sum(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        score: Score,
+        now: Instant,
+    ) -> Result<(Rating, Rating), ConvergenceError> {
+        let first_before = self.clamp_rating(first).rating;
+        let second_before = self.clamp_rating(second).rating;
+
+        let (first, second) = self.update_ratings(first, second, score, now)?;
+        let delta = ((first.rating - first_before) - (second.rating - second_before)) / 2.0;
+
+        Ok((
+            self.clamp_rating(&Rating {
+                rating: first_before + delta,
+                ..first
+            }),
+            self.clamp_rating(&Rating {
+                rating: second_before - delta,
+                ..second
+            }),
+        ))
+    }
+
+    /// Like [`RatingSystem::update_ratings()`], but with independent scores
+    /// for both players, to support scoring rules where the scores of a game
+    /// do not add up to `1.0`.
+    ///
+    /// With `second_score = first_score.opposite()`, this is the same as
+    /// [`RatingSystem::update_ratings()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let first = system.new_rating();
+    /// let second = system.new_rating();
+    /// let now = Instant(0.0);
+    ///
+    /// let score = Score(0.6);
+    /// assert_eq!(
+    ///     system.update_ratings_split(&first, &second, score, score.opposite(), now).unwrap(),
+    ///     system.update_ratings(&first, &second, score, now).unwrap()
+    /// );
+    ///
+    /// // Both players are rewarded.
+    /// let (first_prime, second_prime) = system
+    ///     .update_ratings_split(&first, &second, Score(0.6), Score(0.6), now)
+    ///     .unwrap();
+    /// assert!(first_prime.rating > first.rating);
+    /// assert!(second_prime.rating > second.rating);
+    /// ```
+    pub fn update_ratings_split(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        first_score: Score,
+        second_score: Score,
+        now: Instant,
+    ) -> Result<(Rating, Rating), ConvergenceError> {
+        self.update_ratings_impl(
+            first,
+            second,
+            (first_score, second_score),
+            Some(now),
+            (self.first_advantage, -self.first_advantage),
+        )
+    }
+
+    fn update_ratings_impl(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        (first_score, second_score): (Score, Score),
+        now: Option<Instant>,
+        (first_advantage, second_advantage): (RatingDifference, RatingDifference),
+    ) -> Result<(Rating, Rating), ConvergenceError> {
+        let first = self.clamp_rating(first);
+        let second = self.clamp_rating(second);
+        let first_score = first_score.clamp(Score::LOSS, Score::WIN);
+        let second_score = second_score.clamp(Score::LOSS, Score::WIN);
+
+        let (first_prime, first_delta) =
+            self.update_rating(&first, &second, first_score, now, first_advantage)?;
+        let (second_prime, second_delta) =
+            self.update_rating(&second, &first, second_score, now, second_advantage)?;
+
+        let first_delta = self.discount_loss(&first, first_delta, &second, now);
+        let second_delta = self.discount_loss(&second, second_delta, &first, now);
+
+        let (first_delta, second_delta) = self.regulate(&first, first_delta, &second, second_delta);
+
+        Ok((
+            self.clamp_rating(&Rating {
+                rating: first.rating + first_delta,
+                ..first_prime
+            }),
+            self.clamp_rating(&Rating {
+                rating: second.rating + second_delta,
+                ..second_prime
+            }),
+        ))
+    }
+
+    /// Like [`RatingSystem::update_ratings()`], but returns `None` if the
+    /// game with the given id has already been applied, so that duplicate
+    /// deliveries of the same game do not update ratings twice.
+    ///
+    /// The game is marked as applied only if the update succeeds.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    ///
+    /// use liglicko2::{Instant, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let alice = system.new_rating();
+    /// let bob = system.new_rating();
+    /// let mut seen = HashSet::new();
+    ///
+    /// let first = system.update_ratings_once(&alice, &bob, Score::WIN, Instant(0.0), 42, &mut seen);
+    /// assert!(matches!(first, Some(Ok(_))));
+    ///
+    /// let duplicate = system.update_ratings_once(&alice, &bob, Score::WIN, Instant(0.0), 42, &mut seen);
+    /// assert!(duplicate.is_none());
+    /// ```
+    pub fn update_ratings_once(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        score: Score,
+        now: Instant,
+        game_id: u64,
+        seen: &mut impl DedupSet,
+    ) -> Option<Result<(Rating, Rating), ConvergenceError>> {
+        if seen.contains(game_id) {
+            return None;
+        }
+
+        let result = self.update_ratings(first, second, score, now);
+        if result.is_ok() {
+            seen.insert(game_id);
+        }
+        Some(result)
+    }
+
+    /// Like [`RatingSystem::update_ratings()`], but additionally checks that
+    /// all components of the new ratings are finite.
+    ///
+    /// With default parameters and finite inputs, this check never fails.
+    /// With custom parameters, it provides a clear failure instead of
+    /// silently producing corrupt ratings.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the internal iterative algorithm does not converge, or if
+    /// any component of the new ratings is not finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, OutputError, RatingScalar, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let first = system.new_rating();
+    /// let second = system.new_rating();
+    /// let now = Instant(0.0);
+    ///
+    /// let (checked, _) = system.update_ratings_checked(&first, &second, Score::WIN, now).unwrap();
+    /// let (unchecked, _) = system.update_ratings(&first, &second, Score::WIN, now).unwrap();
+    /// assert_eq!(checked, unchecked);
+    ///
+    /// // Without bounds, an infinite rating is not clamped.
+    /// let unbounded = RatingSystem::builder().unbounded_rating().build();
+    /// let mut infinite = unbounded.new_rating();
+    /// infinite.rating = RatingScalar(f64::INFINITY);
+    /// assert!(matches!(
+    ///     unbounded.update_ratings_checked(&infinite, &second, Score::WIN, now),
+    ///     Err(OutputError::NonFinite)
+    /// ));
+    ///
+    /// // Impossible to converge within a single iteration.
+    /// let impatient = RatingSystem::builder()
+    ///     .max_convergence_iterations(1)
+    ///     .convergence_tolerance(1e-300)
+    ///     .build();
+    /// assert!(matches!(
+    ///     impatient.update_ratings_checked(&first, &second, Score::WIN, now),
+    ///     Err(OutputError::Convergence(_))
+    /// ));
+    /// ```
+    pub fn update_ratings_checked(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        score: Score,
+        now: Instant,
+    ) -> Result<(Rating, Rating), OutputError> {
+        let (first, second) = self.update_ratings(first, second, score, now)?;
+
+        if is_finite(&first) && is_finite(&second) {
+            Ok((first, second))
+        } else {
+            Err(OutputError::NonFinite)
+        }
+    }
+
+    /// Update the ratings of both players, given the margin by which the
+    /// first player won the game between them. A negative margin means that
+    /// the second player won.
+    ///
+    /// The margin is mapped to an effective score
+    /// `0.5 + 0.5 * tanh(margin / margin_scale)`. So a margin of `0.0` is
+    /// exactly a draw, and increasingly decisive margins approach a win or
+    /// loss, moving ratings a bit more than narrow margins.
+    ///
+    /// The effective score is limited to `1e-6..=1.0 - 1e-6`, so that even
+    /// huge margins (where `tanh` rounds to exactly `1.0`) are not treated
+    /// like a plain win or loss.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let first = system.new_rating();
+    /// let second = system.new_rating();
+    /// let now = Instant(0.0);
+    ///
+    /// let (huge, _) = system.update_ratings_margin(&first, &second, 1e9, now).unwrap();
+    /// let (win, _) = system.update_ratings(&first, &second, Score::WIN, now).unwrap();
+    /// assert!(first.rating < huge.rating && huge.rating < win.rating);
+    /// ```
+    pub fn update_ratings_margin(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        margin: f64,
+        now: Instant,
     ) -> Result<(Rating, Rating), ConvergenceError> {
-        let first = self.clamp_rating(first);
-        let second = self.clamp_rating(second);
-        let score = score.clamp(Score::LOSS, Score::WIN);
+        self.update_ratings(first, second, self.margin_score(margin), now)
+    }
 
-        Ok((
-            self.update_rating(&first, &second, score, now, self.first_advantage)?,
-            self.update_rating(
-                &second,
-                &first,
-                score.opposite(),
+    /// Like [`RatingSystem::update_ratings()`], but keeps the inputs in case
+    /// of an error, so that failing cases can be studied.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let first = system.new_rating();
+    /// let second = system.new_rating();
+    /// let now = Instant(1.0);
+    ///
+    /// assert_eq!(
+    ///     system.update_ratings_diagnostic(&first, &second, Score::WIN, now).unwrap(),
+    ///     system.update_ratings(&first, &second, Score::WIN, now).unwrap()
+    /// );
+    ///
+    /// // On failure, the error keeps the inputs.
+    /// let impatient = RatingSystem::builder()
+    ///     .max_convergence_iterations(1)
+    ///     .convergence_tolerance(1e-300)
+    ///     .build();
+    /// let err = impatient
+    ///     .update_ratings_diagnostic(&first, &second, Score::WIN, now)
+    ///     .unwrap_err();
+    /// assert_eq!(err.first, first);
+    /// assert_eq!(err.score, Score::WIN);
+    /// assert!(impatient.update_ratings(&first, &second, Score::WIN, now).is_err());
+    /// ```
+    pub fn update_ratings_diagnostic(
+        &self,
+        first: &Rating,
+        second: &Rating,
+        score: Score,
+        now: Instant,
+    ) -> Result<(Rating, Rating), DetailedConvergenceError> {
+        self.update_ratings(first, second, score, now)
+            .map_err(|err| DetailedConvergenceError {
+                first: first.clone(),
+                second: second.clone(),
+                score,
                 now,
-                -self.first_advantage,
-            )?,
+                advantage: self.first_advantage,
+                residual: err.residual,
+            })
+    }
+
+    fn margin_score(&self, margin: f64) -> Score {
+        const EPSILON: f64 = 1e-6;
+
+        Score(0.5 + 0.5 * f64::tanh(margin / self.margin_scale))
+            .clamp(Score(EPSILON), Score(1.0 - EPSILON))
+    }
+
+    /// Preview by how much the rating of the first player `us` would change,
+    /// given the score of a game against `them`. Includes the effects of the
+    /// regulator and [`RatingSystemBuilder::max_rating_delta()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    pub fn rating_delta_preview(
+        &self,
+        us: &Rating,
+        them: &Rating,
+        score: Score,
+        now: Instant,
+    ) -> Result<RatingDifference, ConvergenceError> {
+        let (us_prime, _) = self.update_ratings(us, them, score, now)?;
+        Ok(us_prime.rating - self.clamp_rating(us).rating)
+    }
+
+    /// Calculate the factor `K` of a classic Elo update `K * (actual -
+    /// expected)`, that would change the rating of the first player `us` by
+    /// the same amount as this rating system, given the score of a game
+    /// against `them`. The Elo expectation is
+    /// [`RatingSystem::expected_score_elo()`].
+    ///
+    /// Not finite if the score matches the Elo expectation exactly.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut established = system.new_rating();
+    /// established.deviation = RatingDifference(50.0);
+    /// let newcomer = system.new_rating();
+    ///
+    /// let now = Instant(0.0);
+    /// let established_k = system.equivalent_elo_k(&established, &established, Score::WIN, now).unwrap();
+    /// let newcomer_k = system.equivalent_elo_k(&newcomer, &established, Score::WIN, now).unwrap();
+    /// assert!(0.0 < established_k && established_k < newcomer_k);
+    /// ```
+    pub fn equivalent_elo_k(
+        &self,
+        us: &Rating,
+        them: &Rating,
+        score: Score,
+        now: Instant,
+    ) -> Result<f64, ConvergenceError> {
+        let delta = self.rating_delta_preview(us, them, score, now)?;
+        let expected = self.expected_score_elo(us, them);
+        Ok(delta.0 / (score.clamp(Score::LOSS, Score::WIN).value() - expected.value()))
+    }
+
+    /// Calculate the rating changes for the first player `us`, in case of a
+    /// win and in case of a loss against `them`. The loss is returned as a
+    /// negative rating difference.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let us = system.new_rating();
+    /// let them = system.new_rating();
+    ///
+    /// let (gain, loss) = system.points_at_risk(&us, &them, Instant(0.0)).unwrap();
+    /// assert!(gain > RatingDifference(0.0));
+    /// assert!(loss < RatingDifference(0.0));
+    /// ```
+    pub fn points_at_risk(
+        &self,
+        us: &Rating,
+        them: &Rating,
+        now: Instant,
+    ) -> Result<(RatingDifference, RatingDifference), ConvergenceError> {
+        Ok((
+            self.rating_delta_preview(us, them, Score::WIN, now)?,
+            self.rating_delta_preview(us, them, Score::LOSS, now)?,
+        ))
+    }
+
+    /// Calculate by how many leaderboard positions the first player `us`
+    /// would climb in case of a win and in case of a loss against `them`.
+    /// Dropping positions is returned as a negative number.
+    ///
+    /// The `population` are the rating values of the other players on the
+    /// leaderboard, sorted in ascending order. It should not include `us`.
+    /// The rank of a rating is the number of players in the population that
+    /// are rated strictly higher.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let us = system.new_rating();
+    /// let them = system.new_rating();
+    ///
+    /// let population = [1000.0, 1450.0, 1490.0, 1510.0, 1550.0, 2000.0].map(RatingScalar);
+    ///
+    /// let (win, loss) = system
+    ///     .expected_rank_change(&us, &them, &population, Instant(0.0))
+    ///     .unwrap();
+    /// assert_eq!(win, 2.0);
+    /// assert_eq!(loss, -2.0);
+    /// ```
+    pub fn expected_rank_change(
+        &self,
+        us: &Rating,
+        them: &Rating,
+        population: &[RatingScalar],
+        now: Instant,
+    ) -> Result<(f64, f64), ConvergenceError> {
+        let rank =
+            |rating: RatingScalar| population.len() - population.partition_point(|r| *r <= rating);
+
+        let before = self.clamp_rating(us).rating;
+        let (gain, loss) = self.points_at_risk(us, them, now)?;
+
+        Ok((
+            rank(before) as f64 - rank(before + gain) as f64,
+            rank(before) as f64 - rank(before + loss) as f64,
         ))
     }
 
+    /// Estimate the expected net rating change of `rating` from a game
+    /// against `opponent` that is purely due to the rating regulator.
+    ///
+    /// The expected rating change is averaged over the outcomes, weighted by
+    /// the expected score, and compared to the expected rating change with
+    /// [`RegulatorMode::None`]. Without the regulator, the expected rating
+    /// change of a game is close to zero, so this quantifies how much the
+    /// regulator inflates the rating of a player over many games.
+    ///
+    /// Zero when [`RatingSystemBuilder::regulator_factor()`] is `1.0`.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingDifference, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let us = system.new_rating();
+    /// let them = system.new_rating();
+    /// let now = Instant(0.0);
+    ///
+    /// let drift = system.regulator_drift_per_game(&us, &them, now).unwrap();
+    /// assert!(drift > RatingDifference(0.0));
+    ///
+    /// let unregulated = RatingSystem::builder().regulator_factor(1.0).build();
+    /// let drift = unregulated.regulator_drift_per_game(&us, &them, now).unwrap();
+    /// assert_eq!(drift, RatingDifference(0.0));
+    /// ```
+    pub fn regulator_drift_per_game(
+        &self,
+        rating: &Rating,
+        opponent: &Rating,
+        now: Instant,
+    ) -> Result<RatingDifference, ConvergenceError> {
+        let expected = self.expected_score(rating, opponent, now).value();
+
+        let expected_delta = |system: &RatingSystem| {
+            let (gain, loss) = system.points_at_risk(rating, opponent, now)?;
+            Ok(expected * gain + (1.0 - expected) * loss)
+        };
+
+        let unregulated = self
+            .to_builder()
+            .regulator_mode(RegulatorMode::None)
+            .build();
+
+        Ok(expected_delta(self)? - expected_delta(&unregulated)?)
+    }
+
+    /// Reconstruct the rating of a player at a past instant, by replaying
+    /// their games against the given opponents, starting from `start`.
+    ///
+    /// Games are replayed in the given order, up to and including games at
+    /// `at`. The deviation of the result is decayed to `at`.
+    ///
+    /// # Errors
+    ///
+    /// See [`RatingSystem::update_ratings()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{Instant, RatingSystem, Score};
+    ///
+    /// let system = RatingSystem::new();
+    /// let start = system.new_rating();
+    /// let opponent = system.new_rating();
+    ///
+    /// let history = [
+    ///     (opponent.clone(), Score::WIN, Instant(1.0)),
+    ///     (opponent.clone(), Score::WIN, Instant(2.0)),
+    /// ];
+    ///
+    /// let before = system.rating_as_of(&history, &start, Instant(1.5)).unwrap();
+    /// let after = system.rating_as_of(&history, &start, Instant(2.5)).unwrap();
+    /// assert!(start.rating < before.rating);
+    /// assert!(before.rating < after.rating);
+    /// assert_eq!(before.at, Instant(1.5));
+    /// ```
+    pub fn rating_as_of(
+        &self,
+        history: &[(Rating, Score, Instant)],
+        start: &Rating,
+        at: Instant,
+    ) -> Result<Rating, ConvergenceError> {
+        let mut rating = self.clamp_rating(start);
+
+        for (opponent, score, now) in history {
+            if *now > at {
+                break;
+            }
+            rating = self.update_ratings(&rating, opponent, *score, *now)?.0;
+        }
+
+        Ok(Rating {
+            deviation: self.preview_deviation(&rating, at),
+            at: if at > rating.at { at } else { rating.at },
+            ..rating
+        })
+    }
+
     fn update_rating(
         &self,
         us: &Rating,
         them: &Rating,
         score: Score,
-        now: Instant,
+        now: Option<Instant>,
         advantage: RatingDifference,
-    ) -> Result<Rating, ConvergenceError> {
+    ) -> Result<(Rating, RatingDifference), ConvergenceError> {
         // Step 2
-        let phi = self.preview_deviation(us, now - Periods(1.0)).to_internal(); // Notable change!
+        let phi = self
+            .update_deviation(us, now)
+            .to_internal(self.internal_rating_scale);
 
         // Step 3
         let their_g = g(self
-            .preview_deviation(them, now - Periods(1.0)) // Notable change!
-            .to_internal());
+            .update_deviation(them, now)
+            .to_internal(self.internal_rating_scale));
 
-        let expected =
-            expectation_value((us.rating - them.rating + advantage).to_internal(), their_g);
+        let expected = expectation_value(
+            (us.rating - them.rating + advantage).to_internal(self.internal_rating_scale),
+            their_g,
+        );
         let v = 1.0 / (their_g.powi(2) * expected.value() * expected.opposite().value());
 
         // Step 4
-        let delta = v * their_g * Score::value(score - expected);
+        let delta =
+            self.discount_volatility_delta(v * their_g * Score::value(score - expected), their_g);
 
         // Step 5.1
         let a = f64::ln(us.volatility.sq());
@@ -409,7 +3705,10 @@ impl RatingSystem {
                 - (x - a) / self.tau.powi(2)
         };
 
-        // Step 5.2
+        // Step 5.2. The bracket is already warm-started: a is derived from
+        // the current volatility, which is the result of the previous update
+        // of this rating. Seeding it from anywhere else would change the
+        // converged result (within tolerance), not just the iteration count.
         let mut big_a = a;
         let mut big_b = if delta.powi(2) > phi.sq() + v {
             f64::ln(delta.powi(2) - phi.sq() - v)
@@ -430,7 +3729,9 @@ impl RatingSystem {
         while f64::abs(big_b - big_a) > self.convergence_tolerance {
             iterations += 1;
             if iterations > self.max_convergence_iterations {
-                return Err(ConvergenceError { _priv: () });
+                return Err(ConvergenceError {
+                    residual: f64::abs(big_b - big_a),
+                });
             }
 
             let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
@@ -448,13 +3749,17 @@ impl RatingSystem {
         }
 
         // Step 5.5
-        let sigma_prime = Volatility(f64::exp(big_a / 2.0));
+        let sigma_prime =
+            self.limit_volatility_increase(us.volatility, Volatility(f64::exp(big_a / 2.0)));
 
         // Step 6
         let phi_star = new_deviation(
             phi,
             sigma_prime,
-            Periods::min(now.elapsed_since(us.at), Periods(1.0)), // Notable change!
+            now.map_or(Periods(0.0), |now| {
+                // Notable change!
+                self.decay_periods(now.elapsed_since(us.at).saturating_to_one())
+            }),
         );
 
         // Step 7
@@ -462,29 +3767,165 @@ impl RatingSystem {
         let mu_prime_diff =
             InternalRatingDifference(phi_prime.sq() * their_g * Score::value(score - expected));
 
-        // Step 8
-        Ok(self.clamp_rating(&Rating {
-            rating: self.regulate(us.rating, mu_prime_diff.to_external()),
-            deviation: phi_prime.to_external(),
-            volatility: sigma_prime,
-            at: now,
-        }))
+        // Step 8 (rating delta is regulated by the caller)
+        Ok((
+            Rating {
+                rating: us.rating,
+                deviation: phi_prime.to_external(self.internal_rating_scale),
+                volatility: sigma_prime,
+                at: now.unwrap_or(us.at),
+            },
+            mu_prime_diff.to_external(self.internal_rating_scale),
+        ))
     }
 
-    fn regulate(&self, rating: RatingScalar, delta: RatingDifference) -> RatingScalar {
-        let factor =
-            if delta > RatingDifference(0.0) && rating < self.default_rating + self.max_deviation {
-                self.regulator_factor
-            } else {
-                1.0
-            };
+    /// Scale the estimated improvement `delta` from step 4 once more by the
+    /// reliability `g` of the opponent rating, if
+    /// [`RatingSystemBuilder::volatility_discounts_opponent_deviation()`] is
+    /// enabled. `delta` only drives the volatility update in step 5, so the
+    /// rating update itself is unaffected.
+    fn discount_volatility_delta(&self, delta: f64, their_g: f64) -> f64 {
+        if self.volatility_discounts_opponent_deviation {
+            their_g * delta
+        } else {
+            delta
+        }
+    }
+
+    /// Limit the new volatility to at most
+    /// [`RatingSystemBuilder::max_volatility_increase_per_game()`] above the
+    /// volatility before the game. In the original Glicko-2 system, a single
+    /// surprising result can raise the volatility, and with it the size of
+    /// the following updates, without bound.
+    fn limit_volatility_increase(&self, before: Volatility, after: Volatility) -> Volatility {
+        Volatility(f64::min(
+            after.0,
+            before.0 + self.max_volatility_increase_per_game,
+        ))
+    }
+
+    /// Deviation of a rating as used by the rating update at `now`, or
+    /// without any decay if `now` is `None`.
+    fn update_deviation(&self, rating: &Rating, now: Option<Instant>) -> RatingDifference {
+        match now {
+            Some(now) => self.pre_update_deviation(rating, now),
+            None => rating.deviation,
+        }
+    }
+
+    fn discount_loss(
+        &self,
+        us: &Rating,
+        delta: RatingDifference,
+        them: &Rating,
+        now: Option<Instant>,
+    ) -> RatingDifference {
+        match self.discount_unestablished_opponent {
+            Some((threshold, factor))
+                if delta.is_negative()
+                    && self.update_deviation(us, now) <= threshold
+                    && self.update_deviation(them, now) > threshold =>
+            {
+                factor * delta
+            }
+            _ => delta,
+        }
+    }
+
+    fn regulate(
+        &self,
+        first: &Rating,
+        first_delta: RatingDifference,
+        second: &Rating,
+        second_delta: RatingDifference,
+    ) -> (RatingDifference, RatingDifference) {
+        let (first_delta, second_delta) = match self.regulator_mode {
+            RegulatorMode::AsymmetricGain => (
+                self.regulator_gain(first, first_delta) * first_delta,
+                self.regulator_gain(second, second_delta) * second_delta,
+            ),
+            RegulatorMode::Symmetric => {
+                let first_excess = (self.regulator_gain(first, first_delta) - 1.0) * first_delta;
+                let second_excess =
+                    (self.regulator_gain(second, second_delta) - 1.0) * second_delta;
+                (
+                    first_delta + first_excess - second_excess,
+                    second_delta + second_excess - first_excess,
+                )
+            }
+            RegulatorMode::None => (first_delta, second_delta),
+        };
+
+        (
+            first_delta.clamp(-self.max_rating_delta, self.max_rating_delta),
+            second_delta.clamp(-self.max_rating_delta, self.max_rating_delta),
+        )
+    }
+
+    fn regulator_gain(&self, rating: &Rating, delta: RatingDifference) -> f64 {
+        if delta.is_positive() && rating.rating < self.default_rating + self.max_deviation {
+            self.regulator_factor
+        } else {
+            1.0
+        }
+    }
+
+    fn decay_periods(&self, elapsed: Periods) -> Periods {
+        if self.discrete_periods {
+            Periods(elapsed.0.floor())
+        } else {
+            elapsed
+        }
+    }
+
+    /// Check if all components of the rating are finite and within the
+    /// bounds of the rating system.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::{RatingScalar, RatingSystem};
+    ///
+    /// let system = RatingSystem::new();
+    /// let mut rating = system.new_rating();
+    /// assert!(system.is_valid(&rating));
+    ///
+    /// rating.rating = RatingScalar(5000.0);
+    /// assert!(!system.is_valid(&rating));
+    /// assert!(system.is_valid(&system.sanitize(&rating)));
+    /// ```
+    pub fn is_valid(&self, rating: &Rating) -> bool {
+        is_finite(rating) && self.clamp_rating(rating) == *rating
+    }
 
-        rating + (factor * delta).clamp(-self.max_rating_delta, self.max_rating_delta)
+    /// Clamp all components of the rating to the bounds of the rating
+    /// system. This happens implicitly whenever a rating is used, but can be
+    /// used to migrate stored ratings to a new configuration.
+    ///
+    /// NaN components are not changed.
+    pub fn sanitize(&self, rating: &Rating) -> Rating {
+        self.clamp_rating(rating)
+    }
+
+    fn clamp_rating_value(&self, rating: RatingScalar) -> RatingScalar {
+        if self.unbounded_rating {
+            rating
+        } else {
+            rating.clamp(self.min_rating, self.max_rating)
+        }
+    }
+
+    fn max_rating_gap(&self) -> RatingDifference {
+        if self.unbounded_rating {
+            RatingDifference(f64::INFINITY)
+        } else {
+            self.max_rating - self.min_rating
+        }
     }
 
-    fn clamp_rating(&self, rating: &Rating) -> Rating {
+    pub(crate) fn clamp_rating(&self, rating: &Rating) -> Rating {
         Rating {
-            rating: rating.rating.clamp(self.min_rating, self.max_rating),
+            rating: self.clamp_rating_value(rating.rating),
             deviation: rating
                 .deviation
                 .clamp(self.min_deviation, self.max_deviation),
@@ -496,11 +3937,114 @@ impl RatingSystem {
     }
 }
 
-fn g(deviation: InternalRatingDifference) -> f64 {
+/// How [`RatingSystemBuilder::regulator_factor()`] is applied to rating
+/// changes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RegulatorMode {
+    /// Multiply gains of players below
+    /// `default_rating + max_deviation` by the regulator factor, leaving
+    /// losses unchanged. This counteracts natural deflation, but inflates the
+    /// total rating of the pool over time. This is what Lichess does.
+    AsymmetricGain,
+    /// Multiply gains by the regulator factor like
+    /// [`RegulatorMode::AsymmetricGain`], but take the excess from the
+    /// opponent, so that regulation does not change the total rating of the
+    /// pair. Does not counteract deflation.
+    ///
+    /// Note that Glicko-2 updates are not zero-sum to begin with, when the
+    /// deviations of the players differ.
+    Symmetric,
+    /// Ignore the regulator factor.
+    None,
+}
+
+/// A rating previewed for a specific point in time, to efficiently calculate
+/// expected scores against many opponents. Constructed with
+/// [`RatingSystem::prepared()`].
+///
+/// Preparing each player once avoids clamping and previewing the ratings
+/// of both players for every pairing, as [`RatingSystem::expected_score()`]
+/// does. Each pairing then only computes `g` of the combined deviation and
+/// the expectation, which costs one square root and one exponential.
+#[derive(Debug, Copy, Clone)]
+pub struct PreparedRating {
+    rating: f64,
+    deviation_sq: f64,
+    first_advantage: f64,
+}
+
+impl PreparedRating {
+    /// Calculate the expected score of this player as the first player
+    /// against `opponent`, like [`RatingSystem::expected_score()`].
+    ///
+    /// Both ratings must be prepared with the same rating system and for the
+    /// same point in time.
+    pub fn expected_score_against(&self, opponent: &PreparedRating) -> Score {
+        expectation_value(
+            InternalRatingDifference(self.rating - opponent.rating + self.first_advantage),
+            1.0 / f64::sqrt(1.0 + 3.0 * (self.deviation_sq + opponent.deviation_sq) / PI.powi(2)),
+        )
+    }
+}
+
+/// Rating value and deviation of a player, previewed for a specific point in
+/// time.
+struct Previewed {
+    rating: RatingScalar,
+    deviation: InternalRatingDifference,
+}
+
+/// Expected deviance in excess of the irreducible outcome uncertainty,
+/// when the true rating difference is normally distributed around `diff`
+/// with standard deviation `phi`.
+fn excess_deviance(
+    InternalRatingDifference(diff): InternalRatingDifference,
+    their_g: f64,
+    InternalRatingDifference(phi): InternalRatingDifference,
+) -> f64 {
+    const STEPS: i32 = 60;
+    const WIDTH: f64 = 6.0;
+
+    let mut total_weight = 0.0;
+    let mut marginal = 0.0;
+    let mut irreducible = 0.0;
+    for i in -STEPS..=STEPS {
+        let z = WIDTH * f64::from(i) / f64::from(STEPS);
+        let weight = f64::exp(-z * z / 2.0);
+        let p = expectation_value(InternalRatingDifference(diff + z * phi), their_g);
+        total_weight += weight;
+        marginal += weight * p.value();
+        irreducible += weight * expected_deviance(p);
+    }
+
+    expected_deviance(Score(marginal / total_weight)) - irreducible / total_weight
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_unstable_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+fn is_finite(rating: &Rating) -> bool {
+    rating.rating.0.is_finite()
+        && rating.deviation.0.is_finite()
+        && rating.volatility.0.is_finite()
+        && rating.at.0.is_finite()
+}
+
+pub(crate) fn g(deviation: InternalRatingDifference) -> f64 {
     1.0 / f64::sqrt(1.0 + 3.0 * deviation.sq() / PI.powi(2))
 }
 
-fn expectation_value(InternalRatingDifference(diff): InternalRatingDifference, g: f64) -> Score {
+pub(crate) fn expectation_value(
+    InternalRatingDifference(diff): InternalRatingDifference,
+    g: f64,
+) -> Score {
     Score(1.0 / (1.0 + f64::exp(-g * diff)))
 }
 
@@ -510,14 +4054,14 @@ fn new_deviation(
     elapsed: Periods,
 ) -> InternalRatingDifference {
     InternalRatingDifference(f64::sqrt(
-        deviation.sq() + Periods::max(elapsed, Periods(0.0)).0 * volatility.sq(),
+        deviation.sq() + elapsed.clamp_non_negative().0 * volatility.sq(),
     ))
 }
 
 /// Glicko-2 rating update algorithm failed to convergence.
 #[derive(Clone)]
 pub struct ConvergenceError {
-    _priv: (),
+    residual: f64,
 }
 
 impl fmt::Debug for ConvergenceError {
@@ -533,3 +4077,87 @@ impl fmt::Display for ConvergenceError {
 }
 
 impl Error for ConvergenceError {}
+
+/// Glicko-2 rating update algorithm failed to converge, with details about
+/// the failing inputs.
+#[derive(Debug, Clone)]
+pub struct DetailedConvergenceError {
+    /// Rating of the first player, before the update.
+    pub first: Rating,
+    /// Rating of the second player, before the update.
+    pub second: Rating,
+    /// Score of the first player.
+    pub score: Score,
+    /// Instant of the update.
+    pub now: Instant,
+    /// Advantage of the first player.
+    pub advantage: RatingDifference,
+    /// Width of the final bracket of the iterative algorithm, to be compared
+    /// with the convergence tolerance.
+    pub residual: f64,
+}
+
+impl fmt::Display for DetailedConvergenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "failed to converge (residual {} at {:?}, score {:?})",
+            self.residual, self.now, self.score
+        )
+    }
+}
+
+impl Error for DetailedConvergenceError {}
+
+/// Instant lies before the last update of a rating.
+#[derive(Clone)]
+pub struct PastInstantError {
+    _priv: (),
+}
+
+impl fmt::Debug for PastInstantError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PastInstantError").finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for PastInstantError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "instant before last rating update")
+    }
+}
+
+impl Error for PastInstantError {}
+
+/// Rating update did not produce valid ratings.
+#[derive(Debug, Clone)]
+pub enum OutputError {
+    /// Glicko-2 rating update algorithm failed to converge.
+    Convergence(ConvergenceError),
+    /// At least one component of the new ratings is not finite.
+    NonFinite,
+}
+
+impl From<ConvergenceError> for OutputError {
+    fn from(err: ConvergenceError) -> OutputError {
+        OutputError::Convergence(err)
+    }
+}
+
+impl fmt::Display for OutputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OutputError::Convergence(err) => err.fmt(f),
+            OutputError::NonFinite => write!(f, "non-finite rating output"),
+        }
+    }
+}
+
+impl Error for OutputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            OutputError::Convergence(err) => Some(err),
+            OutputError::NonFinite => None,
+        }
+    }
+}
@@ -29,6 +29,29 @@ impl Score {
     pub fn clamp(self, min: Score, max: Score) -> Score {
         Score(self.value().clamp(min.value(), max.value()))
     }
+
+    /// Round to the nearest multiple of `step`, within `0.0..=1.0`.
+    ///
+    /// Rounding all displayed probabilities with the same function keeps
+    /// them consistent with each other.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is not positive, or NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use liglicko2::Score;
+    ///
+    /// assert_eq!(Score(0.523).round_to_step(0.05), Score(0.5));
+    /// assert_eq!(Score(0.99).round_to_step(0.25), Score::WIN);
+    /// ```
+    #[must_use]
+    pub fn round_to_step(self, step: f64) -> Score {
+        assert!(step > 0.0);
+        Score((self.value() / step).round() * step).clamp(Score::LOSS, Score::WIN)
+    }
 }
 
 impl Score {
@@ -71,3 +94,34 @@ impl ops::SubAssign<Score> for Score {
         *self = *self - rhs;
     }
 }
+
+/// Scaling constant of the logistic mapping between centipawns and expected
+/// score, as used by Lichess. An advantage of about 300 centipawns
+/// corresponds to an expected score of about `0.75`.
+pub const CENTIPAWN_SCALE: f64 = 0.00368208;
+
+/// Convert an engine evaluation in centipawns (from the point of view of the
+/// player to score) to an expected score, using the logistic mapping with
+/// [`CENTIPAWN_SCALE`].
+///
+/// # Example
+///
+/// ```
+/// use liglicko2::{centipawns_from_score, score_from_centipawns, Score};
+///
+/// assert_eq!(score_from_centipawns(0.0), Score::DRAW);
+/// assert!(score_from_centipawns(300.0) > Score(0.74));
+///
+/// let roundtrip = centipawns_from_score(score_from_centipawns(-120.0));
+/// assert!((roundtrip + 120.0).abs() < 1e-9);
+/// ```
+pub fn score_from_centipawns(cp: f64) -> Score {
+    Score(1.0 / (1.0 + f64::exp(-CENTIPAWN_SCALE * cp)))
+}
+
+/// Convert an expected score to an engine evaluation in centipawns. Inverse
+/// of [`score_from_centipawns()`]. Wins and losses map to infinite
+/// evaluations.
+pub fn centipawns_from_score(score: Score) -> f64 {
+    f64::ln(score.value() / score.opposite().value()) / CENTIPAWN_SCALE
+}